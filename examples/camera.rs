@@ -0,0 +1,46 @@
+//! Hand cameras off to a capture library as they're plugged in.
+//!
+//! Lookit finds cameras that support video capture, and connects to them
+//! along with the format they capture in; a capture crate (like `nokhwa`)
+//! then streams frames from the device, or negotiates another format.
+
+use lookit::{Camera, Feature, Found, Searcher};
+
+/// Where a capture library takes over, keeping the camera open while it runs
+async fn capture(camera: Camera) {
+    let info = camera.info();
+    let format = info.format;
+
+    println!(
+        "{}: {} ({}), {}x{} {}, {} bytes per frame",
+        camera.path().display(),
+        info.card,
+        info.driver,
+        format.width,
+        format.height,
+        String::from_utf8_lossy(&format.fourcc),
+        format.size_image,
+    );
+
+    std::future::pending::<()>().await;
+}
+
+/// Worker for one camera, running until it's disconnected
+async fn camera(found: Found) {
+    match found.connect_camera() {
+        Ok(camera) => capture(camera).await,
+        Err(e) => println!("Couldn't connect to {e}"),
+    }
+}
+
+#[async_main::async_main]
+async fn main(_spawner: impl async_main::Spawn) {
+    let searcher = Searcher::with_camera().require(Feature::Capture);
+
+    lookit::scope(|devices| async move {
+        devices.spawn(searcher, camera);
+
+        std::future::pending::<()>().await;
+    })
+    .await;
+}
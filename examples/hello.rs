@@ -1,20 +1,14 @@
-use lookit::Searcher;
+use lookit::{Kind, Searcher};
 use pasts::prelude::*;
 
 #[async_main::async_main]
 async fn main(_spawner: impl async_main::Spawn) {
-    let mut searcher = Searcher::with_midi();
+    let mut searcher = Searcher::with_all();
     loop {
         let file = searcher.next().await;
 
-        dbg!(&file);
+        let kind = file.kind().map_or("custom", Kind::name);
 
-        let file = file
-            .connect()
-            .or_else(|it| it.connect_input())
-            .or_else(|it| it.connect_output())
-            .ok();
-
-        dbg!(file);
+        println!("{kind}: {}", file.path().display());
     }
 }
@@ -0,0 +1,90 @@
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use crate::snapshot::{escape, unescape};
+
+/// Table of user-friendly labels (like "Left wheel pedal") for devices, keyed
+/// by their stable [`Found::id()`](crate::Found::id).
+///
+/// Clones share the same table, so labels added after it's been handed to a
+/// [`Searcher`](crate::Searcher) with
+/// [`Searcher::aliases()`](crate::Searcher::aliases) apply to devices found
+/// from then on.
+#[derive(Clone, Default)]
+pub struct Aliases(Arc<Mutex<HashMap<String, String>>>);
+
+impl fmt::Debug for Aliases {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.lock().unwrap().iter())
+            .finish()
+    }
+}
+
+impl Aliases {
+    /// Create a new empty alias table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load an alias table saved with [`Aliases::save()`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let invalid = || {
+            io::Error::new(io::ErrorKind::InvalidData, "invalid alias table")
+        };
+        let text = fs::read_to_string(path)?;
+        let aliases = Self::new();
+
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            let (id, label) = line.split_once('\t').ok_or_else(invalid)?;
+            let text = |escaped| String::from_utf8(unescape(escaped)?).ok();
+            let id = text(id).ok_or_else(invalid)?;
+            let label = text(label).ok_or_else(invalid)?;
+
+            aliases.insert(id, label);
+        }
+
+        Ok(aliases)
+    }
+
+    /// Save the alias table to a file, so it persists between runs.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let table = self.0.lock().unwrap();
+        let mut entries: Vec<_> = table.iter().collect();
+        let mut text = String::new();
+
+        entries.sort();
+
+        for (id, label) in entries {
+            text.push_str(&escape(id.as_bytes()));
+            text.push('\t');
+            text.push_str(&escape(label.as_bytes()));
+            text.push('\n');
+        }
+
+        fs::write(path, text)
+    }
+
+    /// Set the label for a device, returning the label it had before.
+    pub fn insert(
+        &self,
+        id: impl Into<String>,
+        label: impl Into<String>,
+    ) -> Option<String> {
+        self.0.lock().unwrap().insert(id.into(), label.into())
+    }
+
+    /// Remove the label for a device, returning it.
+    pub fn remove(&self, id: &str) -> Option<String> {
+        self.0.lock().unwrap().remove(id)
+    }
+
+    /// Get the label for a device.
+    pub fn get(&self, id: &str) -> Option<String> {
+        self.0.lock().unwrap().get(id).cloned()
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    future, io,
+    os::fd::OwnedFd,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use async_io::Async;
+use pasts::notify::Notify;
+
+use crate::{Found, Searcher};
+
+/// Lookit searcher woken by async-io (the reactor behind smol and
+/// async-std).  Yields each device [`Found`].
+///
+/// Created with
+/// [`SearcherBuilder::build_async_io()`](crate::SearcherBuilder::build_async_io),
+/// which registers a duplicate of the searcher's file descriptor with
+/// async-io's [`Async`] instead of watching it with smelling_salts.
+#[derive(Debug)]
+pub struct AsyncIoSearcher {
+    searcher: Searcher,
+    /// Not there when the backend doesn't have a file descriptor, so the
+    /// searcher's only woken by lookit's timers
+    fd: Option<Async<OwnedFd>>,
+}
+
+impl AsyncIoSearcher {
+    pub(crate) fn new(searcher: Searcher) -> io::Result<Self> {
+        let fd = match searcher.as_fd() {
+            Some(fd) => Some(Async::new(fd.try_clone_to_owned()?)?),
+            None => None,
+        };
+
+        Ok(Self { searcher, fd })
+    }
+
+    /// Wait for the next device to be found.
+    ///
+    /// Only fails if async-io can't wait for the file descriptor.
+    pub async fn next(&mut self) -> io::Result<Found> {
+        future::poll_fn(|cx| self.poll_next(cx)).await
+    }
+
+    /// Poll for the next device to be found, for implementing futures and
+    /// streams around the searcher.
+    pub fn poll_next(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<Found>> {
+        loop {
+            if let Poll::Ready(found) =
+                Pin::new(&mut self.searcher).poll_next(cx)
+            {
+                return Poll::Ready(Ok(found));
+            }
+
+            let Some(ref fd) = self.fd else {
+                return Poll::Pending;
+            };
+
+            // Readable since the searcher last read, so read again
+            if let Poll::Ready(()) = fd.poll_readable(cx)? {
+                continue;
+            }
+
+            return Poll::Pending;
+        }
+    }
+
+    /// Deregister the searcher from async-io, and get it back.
+    ///
+    /// It's still not watched by smelling_salts, so only poll it once its
+    /// [file descriptor](Searcher::as_fd) is readable.
+    pub fn into_inner(self) -> Searcher {
+        self.searcher
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::{fs, thread, time::Duration};
+
+    use crate::SearcherBuilder;
+
+    #[test]
+    fn yields_devices() {
+        let dir = std::env::temp_dir()
+            .join(format!("lookit-async-io-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("dev0"), "").unwrap();
+
+        let path = format!("{}/", dir.display());
+        let node = dir.join("dev1");
+        let adding = node.clone();
+        let (initial, added) = async_io::block_on(async {
+            let mut searcher = SearcherBuilder::with_path(path, ["dev"])
+                .build_async_io()
+                .unwrap();
+            let initial = searcher.next().await.unwrap();
+
+            // Added while the task is waiting
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(100));
+                fs::write(adding, "").unwrap();
+            });
+
+            (initial, searcher.next().await.unwrap())
+        });
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(initial.path(), dir.join("dev0"));
+        assert_eq!(added.path(), node);
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    fmt,
+    sync::Arc,
+    task::Wake,
+    thread::{self, Thread},
+};
+
+use pasts::prelude::*;
+
+use crate::{Found, Searcher};
+
+/// Wakes nothing, for polling without waiting
+struct Noop;
+
+impl Wake for Noop {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// Wakes the thread waiting for a device
+pub(crate) struct Unpark(pub(crate) Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Poll once without waiting, for polling from another reactor
+pub(crate) fn try_poll<N>(notify: &mut N) -> Option<N::Event>
+where
+    N: Notify + Unpin,
+{
+    let waker = Arc::new(Noop).into();
+    let mut task = Task::from_waker(&waker);
+
+    match Pin::new(notify).poll_next(&mut task) {
+        Ready(event) => Some(event),
+        Pending => None,
+    }
+}
+
+/// Lookit [`Iterator`].  Blocks the thread until each device is [`Found`].
+///
+/// Created with [`Searcher::blocking_iter()`].  Never returns `None`, so stop
+/// iterating once enough devices are found.
+pub struct BlockingIter {
+    searcher: Searcher,
+}
+
+impl fmt::Debug for BlockingIter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingIter")
+            .field("searcher", &self.searcher)
+            .finish()
+    }
+}
+
+impl BlockingIter {
+    pub(crate) fn new(searcher: Searcher) -> Self {
+        Self { searcher }
+    }
+}
+
+impl Iterator for BlockingIter {
+    type Item = Found;
+
+    fn next(&mut self) -> Option<Found> {
+        let waker = Arc::new(Unpark(thread::current())).into();
+        let mut task = Task::from_waker(&waker);
+
+        loop {
+            if let Ready(found) =
+                Pin::new(&mut self.searcher).poll_next(&mut task)
+            {
+                return Some(found);
+            }
+
+            // Spurious wakeups just poll again
+            thread::park();
+        }
+    }
+}
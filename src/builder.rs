@@ -0,0 +1,282 @@
+#[cfg(all(any(feature = "async-io", feature = "tokio"), unix))]
+use std::io;
+use std::{borrow::Cow, sync::Arc};
+
+#[cfg(all(feature = "async-io", unix))]
+use crate::AsyncIoSearcher;
+#[cfg(all(feature = "mio", unix))]
+use crate::MioSearcher;
+#[cfg(all(feature = "tokio", unix))]
+use crate::TokioSearcher;
+use crate::{
+    filter::Filters, Backend, Dir, Feature, Found, Kind, Searcher, Status,
+    Target,
+};
+
+/// Configuration for creating a [`Searcher`], for options that can't be
+/// changed once it's searching.
+///
+/// Filters can be set here or on the searcher this builds, like
+/// [`Searcher::filter()`].  [`Searcher::limit()`] and [`Searcher::aliases()`]
+/// are only set on the searcher.
+///
+/// ```rust
+/// use lookit::{Backend, Feature, Kind, SearcherBuilder};
+///
+/// // Only cameras plugged in from now on that can capture, without
+/// // rescanning
+/// let searcher = SearcherBuilder::new(Kind::Camera)
+///     .initial(false)
+///     .backends(&[Backend::Inotify, Backend::Uevent])
+///     .require(Feature::Capture)
+///     .build();
+/// # drop(searcher);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SearcherBuilder {
+    pub(crate) target: Target,
+    /// Whether devices already there are found
+    pub(crate) initial: bool,
+    /// Backends that may be used, or empty for any
+    pub(crate) backends: Vec<Backend>,
+    /// Whether the file descriptor is watched by smelling_salts
+    pub(crate) watched: bool,
+    pub(crate) filters: Filters,
+}
+
+impl SearcherBuilder {
+    pub(crate) fn with_target(target: Target) -> Self {
+        let initial = true;
+        let backends = Vec::new();
+        let watched = true;
+        let filters = Filters::default();
+
+        Self {
+            target,
+            initial,
+            backends,
+            watched,
+            filters,
+        }
+    }
+
+    /// Start configuring a searcher for a kind of device.
+    pub fn new(kind: Kind) -> Self {
+        Self::with_target(Target::Kind(kind))
+    }
+
+    /// Start configuring a searcher for device nodes in a custom directory,
+    /// see [`Searcher::with_path()`].
+    pub fn with_path<I>(path: impl Into<Cow<'static, str>>, prefixes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let prefixes = prefixes
+            .into_iter()
+            .map(|prefix| prefix.as_ref().to_string().into())
+            .collect();
+
+        Self::with_target(Target::Custom(Dir::custom(path.into(), prefixes)))
+    }
+
+    /// Set whether devices that are already there when searching starts are
+    /// found (they are by default), rather than only ones added later.
+    pub fn initial(mut self, initial: bool) -> Self {
+        self.initial = initial;
+        self
+    }
+
+    /// Only use these backends, for example to avoid rescanning when devices
+    /// can't be watched for.
+    ///
+    /// Backends are still tried in the platform's order of preference.  If
+    /// none of them can be used, nothing is found.
+    pub fn backends(mut self, backends: &[Backend]) -> Self {
+        self.backends = backends.to_vec();
+        self
+    }
+
+    /// Set whether the searcher's file descriptor is watched by smelling_salts
+    /// (it is by default).
+    ///
+    /// Turn this off when registering [`Searcher::as_fd()`] with another
+    /// reactor (like tokio's `AsyncFd`), so smelling_salts' reactor thread
+    /// isn't started alongside it.  Nothing else wakes an unwatched searcher,
+    /// so only poll it (or call [`Searcher::try_next()`]) once its file
+    /// descriptor is readable.  Searchers [restored](Searcher::restore) from
+    /// a snapshot are always watched.
+    ///
+    /// ```rust
+    /// use lookit::{Kind, SearcherBuilder};
+    ///
+    /// let searcher = SearcherBuilder::new(Kind::Input).watched(false).build();
+    /// # drop(searcher);
+    /// ```
+    pub fn watched(mut self, watched: bool) -> Self {
+        self.watched = watched;
+        self
+    }
+
+    /// Only find devices whose kernel driver supports a feature, see
+    /// [`Searcher::require()`].
+    pub fn require(mut self, feature: Feature) -> Self {
+        self.filters.features.push(feature);
+        self
+    }
+
+    /// Only find devices with a name matching a glob pattern, see
+    /// [`Searcher::matching()`].
+    pub fn matching(mut self, pattern: impl Into<Cow<'static, str>>) -> Self {
+        self.filters.patterns.push(pattern.into());
+        self
+    }
+
+    /// Only find devices with this USB vendor ID, see [`Searcher::vendor()`].
+    ///
+    /// ```rust
+    /// use lookit::{Kind, SearcherBuilder};
+    ///
+    /// // Logitech C920 webcams
+    /// let searcher = SearcherBuilder::new(Kind::Camera)
+    ///     .vendor(0x046d)
+    ///     .product(0x082d)
+    ///     .build();
+    /// # drop(searcher);
+    /// ```
+    pub fn vendor(mut self, vendor: u16) -> Self {
+        self.filters.vendor = Some(vendor);
+        self
+    }
+
+    /// Only find devices with this USB product ID, see
+    /// [`Searcher::product()`].
+    pub fn product(mut self, product: u16) -> Self {
+        self.filters.product = Some(product);
+        self
+    }
+
+    /// Only find devices that `predicate` returns `true` for, see
+    /// [`Searcher::filter()`].
+    pub fn filter(
+        mut self,
+        predicate: impl Fn(&Found) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.filters.predicates.push(Arc::new(predicate));
+        self
+    }
+
+    /// Check if a backend may be used.
+    pub(crate) fn allows(&self, backend: Backend) -> bool {
+        self.backends.is_empty() || self.backends.contains(&backend)
+    }
+
+    /// Create the searcher.
+    ///
+    /// If none of the backends can be used, the searcher never finds anything
+    /// (see [`Searcher::status()`]).
+    pub fn build(self) -> Searcher {
+        Searcher::build(&self)
+    }
+
+    /// Create the searcher, or get the [`Status`] explaining why none of the
+    /// backends can be used.
+    pub fn try_build(self) -> Result<Searcher, Status> {
+        let searcher = Searcher::build(&self);
+
+        if searcher.is_active() {
+            Ok(searcher)
+        } else {
+            Err(searcher.into_status())
+        }
+    }
+
+    /// Create the searcher, registered with tokio's reactor instead of
+    /// smelling_salts (requires the `tokio` feature).
+    ///
+    /// Backends without a file descriptor (like [`Backend::Scan`]) are woken
+    /// by lookit's timers instead.  Fails if the file descriptor can't be
+    /// registered.
+    ///
+    /// # Panics
+    /// If called outside of a tokio runtime with IO enabled.
+    ///
+    /// ```rust, ignore
+    /// use lookit::{Kind, SearcherBuilder};
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let mut searcher = SearcherBuilder::new(Kind::Input).build_tokio()?;
+    ///
+    ///     loop {
+    ///         println!("Found {:?}", searcher.next().await?);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(all(feature = "tokio", unix))]
+    pub fn build_tokio(self) -> io::Result<TokioSearcher> {
+        TokioSearcher::new(self.watched(false).build())
+    }
+
+    /// Create the searcher, registered with async-io's reactor (used by smol
+    /// and async-std) instead of smelling_salts (requires the `async-io`
+    /// feature).
+    ///
+    /// Backends without a file descriptor (like [`Backend::Scan`]) are woken
+    /// by lookit's timers instead.  Fails if the file descriptor can't be
+    /// registered.
+    ///
+    /// ```rust, ignore
+    /// use lookit::{Kind, SearcherBuilder};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     smol::block_on(async {
+    ///         let mut searcher =
+    ///             SearcherBuilder::new(Kind::Serial).build_async_io()?;
+    ///
+    ///         loop {
+    ///             println!("Found {:?}", searcher.next().await?);
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    #[cfg(all(feature = "async-io", unix))]
+    pub fn build_async_io(self) -> io::Result<AsyncIoSearcher> {
+        AsyncIoSearcher::new(self.watched(false).build())
+    }
+
+    /// Create the searcher as a mio [`Source`](mio::event::Source), for
+    /// event loops without an async runtime (requires the `mio` feature).
+    ///
+    /// It's not watched by smelling_salts.  Registering it fails when the
+    /// backend doesn't have a file descriptor (like [`Backend::Scan`]).
+    ///
+    /// ```rust, ignore
+    /// use lookit::{Kind, SearcherBuilder};
+    /// use mio::{Events, Interest, Poll, Token};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut searcher = SearcherBuilder::new(Kind::Joystick).build_mio();
+    ///     let mut poll = Poll::new()?;
+    ///     let mut events = Events::with_capacity(16);
+    ///
+    ///     poll.registry().register(
+    ///         &mut searcher,
+    ///         Token(0),
+    ///         Interest::READABLE,
+    ///     )?;
+    ///
+    ///     loop {
+    ///         while let Some(found) = searcher.try_next() {
+    ///             println!("Found {found:?}");
+    ///         }
+    ///
+    ///         poll.poll(&mut events, None)?;
+    ///     }
+    /// }
+    /// ```
+    #[cfg(all(feature = "mio", unix))]
+    pub fn build_mio(self) -> MioSearcher {
+        MioSearcher::new(self.watched(false).build())
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(target_os = "linux")]
+use std::os::fd::{AsFd, BorrowedFd};
+use std::path::{Path, PathBuf};
+
+use crate::{Access, Device, VideoInfo};
+
+/// A connected camera, along with its capture metadata, from
+/// [`Found::connect_camera()`](crate::Found::connect_camera).
+///
+/// This is what gets handed off to a capture library (like `nokhwa`), which
+/// can start streaming in the [format](VideoInfo::format) the camera's already
+/// set to, or negotiate another one.
+#[derive(Debug)]
+pub struct Camera {
+    device: Device,
+    access: Access,
+    path: PathBuf,
+    info: VideoInfo,
+}
+
+impl Camera {
+    pub(crate) fn new(
+        device: Device,
+        access: Access,
+        path: PathBuf,
+        info: VideoInfo,
+    ) -> Self {
+        Self {
+            device,
+            access,
+            path,
+            info,
+        }
+    }
+
+    /// Get the path the camera was found at.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the access mode the camera was connected with.
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    /// Get the capture metadata read from the driver.
+    pub fn info(&self) -> &VideoInfo {
+        &self.info
+    }
+
+    /// Get the connected device.
+    pub fn device(&mut self) -> &mut Device {
+        &mut self.device
+    }
+
+    /// Take the connected device, for capturing from.
+    pub fn into_device(self) -> Device {
+        self.device
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl AsFd for Camera {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.device.as_fd()
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(any(unix, target_os = "wasi"))]
+use std::os::fd::BorrowedFd;
+use std::{collections::HashSet, fmt, path::PathBuf};
+
+use pasts::prelude::*;
+
+use crate::{blocking, Found, Searcher};
+
+/// A device being connected or disconnected, from [`Changes`].
+#[derive(Debug)]
+pub enum Event {
+    /// A device was found
+    Connected(Found),
+    /// A device found earlier was removed, with its platform path
+    Disconnected(PathBuf),
+}
+
+/// Lookit [`Notify`].  Lets you know when a device is [`Found`], and when it's
+/// removed again.
+pub struct Changes {
+    searcher: Searcher,
+    /// Paths of devices found, that haven't been removed since
+    connected: HashSet<PathBuf>,
+    /// Device found after devices that were removed before it, which is
+    /// delivered once they are
+    found: Option<Found>,
+}
+
+impl fmt::Debug for Changes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Changes")
+            .field("searcher", &self.searcher)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Changes {
+    pub(crate) fn new(searcher: Searcher) -> Self {
+        let connected = HashSet::new();
+        let found = None;
+
+        Self {
+            searcher,
+            connected,
+            found,
+        }
+    }
+
+    /// Get the path of the next device removed, out of the ones found
+    fn removed(&mut self) -> Option<PathBuf> {
+        let removed = self.searcher.removed.as_mut()?;
+
+        while let Some(path) = removed.pop_front() {
+            if self.connected.remove(&path) {
+                return Some(path);
+            }
+        }
+
+        None
+    }
+
+    /// Get the next change, or `None` if there isn't one yet, without
+    /// waiting (see [`Searcher::try_next()`]).
+    pub fn try_next(&mut self) -> Option<Event> {
+        blocking::try_poll(self)
+    }
+
+    /// Get the file descriptor that becomes readable when there are more
+    /// changes, see [`Searcher::as_fd()`].
+    #[cfg(any(unix, target_os = "wasi"))]
+    pub fn as_fd(&self) -> Option<BorrowedFd<'_>> {
+        self.searcher.as_fd()
+    }
+}
+
+impl Notify for Changes {
+    type Event = Event;
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Event> {
+        let this = self.get_mut();
+
+        if let Some(path) = this.removed() {
+            return Ready(Event::Disconnected(path));
+        }
+
+        // Removals are noticed while searching for new devices
+        let found = match this.found.take() {
+            Some(found) => found,
+            None => {
+                let Ready(found) = Pin::new(&mut this.searcher).poll_next(task)
+                else {
+                    return this.removed().map_or(Pending, |path| {
+                        Ready(Event::Disconnected(path))
+                    });
+                };
+
+                // Removals noticed along with a device happened before it was
+                // found, so a device re-added under the same path stays
+                // connected
+                if let Some(path) = this.removed() {
+                    this.found = Some(found);
+
+                    return Ready(Event::Disconnected(path));
+                }
+
+                found
+            }
+        };
+
+        this.connected.insert(found.path().to_path_buf());
+
+        Ready(Event::Connected(found))
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::SearcherBuilder;
+
+    #[test]
+    fn delivers_events_in_order() {
+        let dir = std::env::temp_dir()
+            .join(format!("lookit-changes-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+
+        let path = format!("{}/", dir.display());
+        // Unwatched, since it's only driven with `try_next()`
+        let mut changes = SearcherBuilder::with_path(path, ["dev"])
+            .watched(false)
+            .build()
+            .changes();
+        let node = dir.join("dev0");
+        let mut events = Vec::new();
+
+        // Finish the initial search first, which would find the node too
+        assert!(changes.try_next().is_none());
+
+        // Created, removed and created again all in one batch of events
+        fs::write(&node, "").unwrap();
+        fs::remove_file(&node).unwrap();
+        fs::write(&node, "").unwrap();
+
+        while let Some(event) = changes.try_next() {
+            events.push(match event {
+                Event::Connected(found) => (true, found.path().to_path_buf()),
+                Event::Disconnected(path) => (false, path),
+            });
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            events,
+            [(true, node.clone()), (false, node.clone()), (true, node)],
+        );
+    }
+}
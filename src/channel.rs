@@ -0,0 +1,106 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, Thread},
+};
+
+use pasts::prelude::*;
+
+use crate::{blocking::Unpark, mailbox::Mailbox, Found, Searcher};
+
+/// State shared between the searcher thread and its receivers
+#[derive(Debug)]
+struct Shared {
+    /// Devices found by the searcher thread, and the receivers waiting for
+    /// them
+    mailbox: Mutex<Mailbox<Found>>,
+    /// Number of receivers, the thread stops once there are none
+    receivers: AtomicUsize,
+}
+
+/// The searcher thread
+fn search(mut searcher: Searcher, shared: Arc<Shared>) {
+    let waker = Arc::new(Unpark(thread::current())).into();
+    let mut task = Task::from_waker(&waker);
+
+    while shared.receivers.load(Ordering::Acquire) != 0 {
+        let Ready(found) = Pin::new(&mut searcher).poll_next(&mut task) else {
+            // Woken when a device is found, or a receiver is dropped
+            thread::park();
+            continue;
+        };
+        let wakers = shared.mailbox.lock().unwrap().send(found);
+
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+/// Lookit [`Notify`].  Receives devices [`Found`] by a searcher running on its
+/// own thread.
+///
+/// Created with [`Searcher::into_channel()`].  Receivers can be cloned and sent
+/// to other threads, and each device found is received by one of them.  The
+/// thread stops once every receiver has been dropped.
+pub struct Receiver {
+    shared: Arc<Shared>,
+    thread: Thread,
+}
+
+impl fmt::Debug for Receiver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+impl Receiver {
+    pub(crate) fn new(searcher: Searcher) -> Self {
+        let mailbox = Mutex::default();
+        let receivers = AtomicUsize::new(1);
+        let shared = Arc::new(Shared { mailbox, receivers });
+        let searching = Arc::clone(&shared);
+        let thread = thread::spawn(move || search(searcher, searching))
+            .thread()
+            .clone();
+
+        Self { shared, thread }
+    }
+
+    /// Get the next device found, or `None` if there isn't one yet, without
+    /// waiting.
+    pub fn try_recv(&self) -> Option<Found> {
+        self.shared.mailbox.lock().unwrap().queue.pop_front()
+    }
+}
+
+impl Clone for Receiver {
+    fn clone(&self) -> Self {
+        let shared = Arc::clone(&self.shared);
+        let thread = self.thread.clone();
+
+        shared.receivers.fetch_add(1, Ordering::AcqRel);
+
+        Self { shared, thread }
+    }
+}
+
+impl Drop for Receiver {
+    fn drop(&mut self) {
+        if self.shared.receivers.fetch_sub(1, Ordering::AcqRel) == 1 {
+            // Let the thread stop
+            self.thread.unpark();
+        }
+    }
+}
+
+impl Notify for Receiver {
+    type Event = Found;
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Found> {
+        self.shared.mailbox.lock().unwrap().poll_recv(task)
+    }
+}
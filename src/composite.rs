@@ -0,0 +1,231 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use pasts::prelude::*;
+
+use crate::{timer::Timer, Found, Interface, Platform, Searcher};
+
+/// Device nodes belonging to the same physical device, found by a [`Batcher`].
+#[derive(Debug)]
+pub struct Composite {
+    parent: Option<String>,
+    nodes: Vec<Found>,
+}
+
+impl Composite {
+    /// Get the platform path of the physical parent device, if known.
+    ///
+    /// On Linux, this is a sysfs directory.
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+
+    /// Get the device nodes, in the order they were found.
+    pub fn nodes(&self) -> &[Found] {
+        &self.nodes
+    }
+
+    /// Get the main device node, for when only one needs to be opened.
+    ///
+    /// On Linux, this is the node with index 0 on its physical device (for
+    /// example, a camera's video capture node rather than its metadata capture
+    /// node).  Otherwise, it's the first node found.
+    pub fn primary(&self) -> &Found {
+        &self.nodes[self.primary_index()]
+    }
+
+    /// Take the main device node, see [`Composite::primary()`].
+    pub fn into_primary(mut self) -> Found {
+        self.nodes.swap_remove(self.primary_index())
+    }
+
+    /// Get the index of the main device node
+    fn primary_index(&self) -> usize {
+        self.nodes
+            .iter()
+            .position(|found| found.with_path(Platform::index) == Some(0))
+            .unwrap_or_default()
+    }
+
+    /// Take the device nodes, in the order they were found.
+    pub fn into_nodes(self) -> Vec<Found> {
+        self.nodes
+    }
+}
+
+/// A composite that's still within its grace period
+struct Batch {
+    deadline: Instant,
+    composite: Composite,
+}
+
+/// Lookit [`Notify`].  Gathers [`Found`] device nodes into [`Composite`]s.
+pub struct Batcher {
+    searcher: Searcher,
+    grace_period: Duration,
+    batches: Vec<Batch>,
+    timer: Timer,
+}
+
+impl fmt::Debug for Batcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Batcher")
+            .field("searcher", &self.searcher)
+            .field("grace_period", &self.grace_period)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Batcher {
+    pub(crate) fn new(searcher: Searcher, grace_period: Duration) -> Self {
+        let batches = Vec::new();
+        let timer = Timer::default();
+
+        Self {
+            searcher,
+            grace_period,
+            batches,
+            timer,
+        }
+    }
+
+    /// Add a found node to the batch for its parent, or start a new batch
+    fn add(&mut self, found: Found) {
+        let parent = found.with_path(Platform::parent);
+
+        self.add_to(parent, found);
+    }
+
+    /// Add a found node to the batch for a parent, or start a new batch
+    fn add_to(&mut self, parent: Option<String>, found: Found) {
+        let batch = self
+            .batches
+            .iter_mut()
+            .find(|batch| parent.is_some() && batch.composite.parent == parent);
+
+        if let Some(batch) = batch {
+            batch.composite.nodes.push(found);
+            return;
+        }
+
+        let deadline = Instant::now() + self.grace_period;
+        let nodes = vec![found];
+        let composite = Composite { parent, nodes };
+
+        self.batches.push(Batch {
+            deadline,
+            composite,
+        });
+    }
+}
+
+impl Notify for Batcher {
+    type Event = Composite;
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Composite> {
+        let this = self.get_mut();
+
+        loop {
+            while let Ready(found) =
+                Pin::new(&mut this.searcher).poll_next(task)
+            {
+                this.add(found);
+            }
+
+            // Batches are kept in deadline order
+            let Some(batch) = this.batches.first() else {
+                this.timer.clear();
+                return Pending;
+            };
+
+            if batch.deadline <= Instant::now() {
+                return Ready(this.batches.remove(0).composite);
+            }
+
+            this.timer.set(batch.deadline);
+
+            if Pin::new(&mut this.timer).poll_next(task).is_pending() {
+                return Pending;
+            }
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+        thread,
+    };
+
+    use super::*;
+    use crate::{blocking, SearcherBuilder};
+
+    const GRACE_PERIOD: Duration = Duration::from_millis(200);
+
+    /// Batch devices from an empty directory, so only the ones added by the
+    /// test are batched
+    fn batcher(name: &str) -> (Batcher, PathBuf) {
+        let dir = std::env::temp_dir()
+            .join(format!("lookit-composite-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+
+        let path = format!("{}/", dir.display());
+        let searcher = SearcherBuilder::with_path(path, ["dev"])
+            .watched(false)
+            .build();
+
+        (searcher.batch(GRACE_PERIOD), dir)
+    }
+
+    /// Get the paths of a composite's nodes
+    fn paths(composite: &Composite) -> Vec<&Path> {
+        composite.nodes().iter().map(Found::path).collect()
+    }
+
+    #[test]
+    fn siblings_grouped() {
+        let (mut batcher, dir) = batcher("siblings");
+        let parent = Some("/sys/devices/usb1/1-1".to_string());
+        let other = Some("/sys/devices/usb1/1-2".to_string());
+
+        batcher.add_to(parent.clone(), Found::new(dir.join("dev0")));
+        batcher.add_to(other.clone(), Found::new(dir.join("dev1")));
+        batcher.add_to(parent.clone(), Found::new(dir.join("dev2")));
+        assert!(blocking::try_poll(&mut batcher).is_none());
+        thread::sleep(GRACE_PERIOD);
+
+        let first = blocking::try_poll(&mut batcher).unwrap();
+        let second = blocking::try_poll(&mut batcher).unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(first.parent(), parent.as_deref());
+        assert_eq!(paths(&first), [dir.join("dev0"), dir.join("dev2")]);
+        assert_eq!(second.parent(), other.as_deref());
+        assert_eq!(paths(&second), [dir.join("dev1")]);
+        assert!(blocking::try_poll(&mut batcher).is_none());
+    }
+
+    #[test]
+    fn lone_device_after_timeout() {
+        let (mut batcher, dir) = batcher("lone");
+
+        batcher.add_to(None, Found::new(dir.join("dev0")));
+        // Nodes without a known parent aren't grouped with each other
+        batcher.add_to(None, Found::new(dir.join("dev1")));
+        assert!(blocking::try_poll(&mut batcher).is_none());
+        thread::sleep(GRACE_PERIOD);
+
+        let first = blocking::try_poll(&mut batcher).unwrap();
+        let second = blocking::try_poll(&mut batcher).unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(first.parent(), None);
+        assert_eq!(paths(&first), [dir.join("dev0")]);
+        assert_eq!(paths(&second), [dir.join("dev1")]);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{Access, Events};
+
+/// Options for connecting to a [`Found`](crate::Found) device, with
+/// [`Found::connect_with()`](crate::Found::connect_with).
+///
+/// By default, devices are opened for input and output, non-blocking, and
+/// closed on `exec()`, like [`Found::connect()`](crate::Found::connect).
+///
+/// ```rust, no_run
+/// use lookit::{ConnectOptions, Searcher};
+/// use pasts::prelude::*;
+///
+/// #[async_main::async_main]
+/// async fn main(_spawner: impl async_main::Spawn) {
+///     let found = Searcher::with_storage().next().await;
+///     // Other processes can't open the disk while it's connected
+///     let options = ConnectOptions::new().exclusive(true);
+///
+///     if let Ok((device, access)) = found.connect_with(options) {
+///         dbg!(device, access);
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ConnectOptions {
+    pub(crate) events: Events,
+    pub(crate) nonblocking: bool,
+    pub(crate) cloexec: bool,
+    pub(crate) exclusive: bool,
+    pub(crate) append: bool,
+    pub(crate) flags: i32,
+}
+
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConnectOptions {
+    /// Create the default options.
+    pub const fn new() -> Self {
+        Self {
+            events: Events::All(),
+            nonblocking: true,
+            cloexec: true,
+            exclusive: false,
+            append: false,
+            flags: 0,
+        }
+    }
+
+    pub(crate) const fn with_events(events: Events) -> Self {
+        let mut options = Self::new();
+
+        options.events = events;
+        options
+    }
+
+    /// Connect to the device for input only.
+    pub const fn input(mut self) -> Self {
+        self.events = Events::Read();
+        self
+    }
+
+    /// Connect to the device for output only.
+    pub const fn output(mut self) -> Self {
+        self.events = Events::Write();
+        self
+    }
+
+    /// Set whether reads and writes return instead of waiting (`O_NONBLOCK`).
+    ///
+    /// Devices are watched for being ready either way, but blocking ones can
+    /// stall the executor if they're read from before they're ready.
+    pub const fn nonblocking(mut self, nonblocking: bool) -> Self {
+        self.nonblocking = nonblocking;
+        self
+    }
+
+    /// Set whether the device is closed when the process runs `exec()`
+    /// (`O_CLOEXEC`).
+    pub const fn cloexec(mut self, cloexec: bool) -> Self {
+        self.cloexec = cloexec;
+        self
+    }
+
+    /// Set whether to open the device exclusively (`O_EXCL`).
+    ///
+    /// On Linux, this keeps other processes from opening block devices (like
+    /// storage) while connected.
+    pub const fn exclusive(mut self, exclusive: bool) -> Self {
+        self.exclusive = exclusive;
+        self
+    }
+
+    /// Set whether writes go to the end of the device (`O_APPEND`), when
+    /// connecting for output.
+    pub const fn append(mut self, append: bool) -> Self {
+        self.append = append;
+        self
+    }
+
+    /// Add raw flags for opening the device, on Unix platforms.
+    pub const fn flags(mut self, flags: i32) -> Self {
+        self.flags |= flags;
+        self
+    }
+
+    /// Get the access mode granted by connecting with these options
+    pub(crate) const fn access(&self) -> Access {
+        self.events.access()
+    }
+}
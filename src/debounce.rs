@@ -0,0 +1,103 @@
+use std::{
+    collections::VecDeque,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use pasts::prelude::*;
+
+use crate::{timer::Timer, Found};
+
+/// A device waiting for events for its path to settle
+#[derive(Debug)]
+struct Settling {
+    deadline: Instant,
+    found: Found,
+}
+
+/// Coalesces devices found for the same path within a window
+#[derive(Debug)]
+pub(crate) struct Debounce {
+    window: Duration,
+    settling: Vec<Settling>,
+    timer: Timer,
+}
+
+impl Debounce {
+    pub(crate) fn new(window: Duration) -> Self {
+        let settling = Vec::new();
+        let timer = Timer::default();
+
+        Self {
+            window,
+            settling,
+            timer,
+        }
+    }
+
+    /// Add a found device, restarting the window if its path is settling
+    pub(crate) fn add(&mut self, found: Found) {
+        let deadline = Instant::now() + self.window;
+        let settling = self
+            .settling
+            .iter_mut()
+            .find(|settling| settling.found.path == found.path);
+
+        // The first one is kept, so its timestamp is of the first event
+        if let Some(settling) = settling {
+            settling.deadline = deadline;
+            return;
+        }
+
+        self.settling.push(Settling { deadline, found });
+    }
+
+    /// Forget devices removed before they settled
+    pub(crate) fn remove(&mut self, paths: &VecDeque<PathBuf>) {
+        self.settling
+            .retain(|settling| !paths.contains(&settling.found.path));
+    }
+
+    /// Discard the devices that haven't settled yet
+    pub(crate) fn clear(&mut self) {
+        self.settling.clear();
+        self.timer.clear();
+    }
+
+    /// Take the devices that haven't settled yet, in the order they were found
+    pub(crate) fn into_pending(self) -> impl Iterator<Item = Found> {
+        self.settling.into_iter().map(|settling| settling.found)
+    }
+}
+
+impl Notify for Debounce {
+    type Event = Found;
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Found> {
+        let this = self.get_mut();
+
+        loop {
+            // Restarted windows may put deadlines out of order
+            let Some((index, deadline)) = this
+                .settling
+                .iter()
+                .enumerate()
+                .map(|(index, settling)| (index, settling.deadline))
+                .min_by_key(|&(_, deadline)| deadline)
+            else {
+                this.timer.clear();
+                return Pending;
+            };
+
+            if deadline <= Instant::now() {
+                return Ready(this.settling.remove(index).found);
+            }
+
+            this.timer.set(deadline);
+
+            if Pin::new(&mut this.timer).poll_next(task).is_pending() {
+                return Pending;
+            }
+        }
+    }
+}
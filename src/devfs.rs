@@ -0,0 +1,119 @@
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "wasi"
+))]
+use crate::Dir;
+use crate::Kind;
+
+/// Kinds of devices that can only be told apart from others using sysfs (by
+/// USB interface class or vendor, HID usage page, or DRM hotplug uevents), so
+/// they can't be found by scanning directories on other platforms
+#[cfg(not(target_os = "linux"))]
+pub(crate) const SYSFS_ONLY: &[Kind] = &[
+    Kind::Display,
+    Kind::Scanner,
+    Kind::Smartcard,
+    Kind::Fingerprint,
+    Kind::PowerDevice,
+    Kind::Braille,
+];
+
+/// USB vendors whose devices are fingerprint readers
+///
+/// Fingerprint readers don't have a USB interface class of their own.
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "wasi"))]
+const FINGERPRINT: &[u16] = &[
+    0x06cb, // Synaptics
+    0x08ff, // AuthenTec
+    0x10a5, // FPC
+    0x138a, // Validity Sensors
+    0x147e, // Upek
+    0x1c7a, // LighTuning (Egis)
+    0x27c6, // Goodix
+    0x2808, // FocalTech
+];
+
+/// Directories where device nodes for a kind are found, to try in order
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "wasi"))]
+pub(crate) fn dirs(kind: Kind) -> Vec<Dir> {
+    use Kind::*;
+
+    match kind {
+        Input => vec![Dir::new("/dev/input/", &["event"])],
+        Audio => vec![Dir::new("/dev/snd/", &["pcm"])],
+        Midi => vec![
+            Dir::new("/dev/snd/", &["midi", "seq"]),
+            Dir::new("/dev/", &["midi"]),
+        ],
+        Camera => vec![Dir::new("/dev/", &["video"])],
+        Serial => vec![Dir::new("/dev/", &["ttyUSB", "ttyACM", "rfcomm"])],
+        Usb => vec![Dir::new("/dev/bus/usb/", &[""]).recursive()],
+        Hid => vec![Dir::new("/dev/", &["hidraw"])],
+        Bluetooth => vec![Dir::new("/sys/class/bluetooth/", &["hci"])],
+        Joystick => vec![Dir::new("/dev/input/", &["js"])],
+        Storage => {
+            vec![Dir::new("/dev/", &["sd", "nvme", "mmcblk"]).block()]
+        }
+        SdCard => vec![Dir::new("/dev/", &["mmcblk"]).block().whole()],
+        Network => vec![Dir::new("/sys/class/net/", &[""])],
+        Gpu => vec![Dir::new("/dev/dri/", &["card", "renderD"])],
+        Display => vec![Dir::new("/sys/class/drm/", &["card"])],
+        // USB printers are in a subdirectory that's only there while one is
+        // plugged in
+        Printer => vec![Dir::new("/dev/", &["lp"]).within(&["usb"])],
+        Scanner => {
+            vec![Dir::new("/dev/bus/usb/", &[""]).recursive().class(0x06)]
+        }
+        Tuner => {
+            vec![Dir::new("/dev/dvb/", &["frontend", "demux"]).recursive()]
+        }
+        Cec => vec![Dir::new("/dev/", &["cec"])],
+        Sensor => vec![Dir::new("/dev/", &["iio:device"])],
+        Gpio => vec![Dir::new("/dev/", &["gpiochip"])],
+        I2c => vec![Dir::new("/dev/", &["i2c-"])],
+        Spi => vec![Dir::new("/dev/", &["spidev"])],
+        Can => vec![Dir::new("/sys/class/net/", &["can", "vcan"])],
+        Tpm => vec![Dir::new("/dev/", &["tpm"])],
+        Watchdog => vec![Dir::new("/dev/", &["watchdog"])],
+        Smartcard => {
+            vec![Dir::new("/dev/bus/usb/", &[""]).recursive().class(0x0b)]
+        }
+        Nfc => vec![Dir::new("/sys/class/nfc/", &["nfc"])],
+        Rtc => vec![Dir::new("/dev/", &["rtc"])],
+        Framebuffer => vec![Dir::new("/dev/", &["fb"])],
+        Media => vec![Dir::new("/dev/", &["media"])],
+        Subdev => vec![Dir::new("/dev/", &["v4l-subdev"])],
+        Radio => vec![Dir::new("/dev/", &["radio"])],
+        Lirc => vec![Dir::new("/dev/", &["lirc"])],
+        SoundCard => vec![Dir::new("/dev/snd/", &["controlC"])],
+        Hwdep => vec![Dir::new("/dev/snd/", &["hwC"])],
+        Compress => vec![Dir::new("/dev/snd/", &["comprC"])],
+        Uinput => vec![Dir::new("/dev/", &["uinput"]).writable()],
+        Fingerprint => {
+            vec![Dir::new("/dev/bus/usb/", &[""])
+                .recursive()
+                .vendors(FINGERPRINT)]
+        }
+        PowerSupply => vec![Dir::new("/sys/class/power_supply/", &[""])],
+        Hwmon => vec![Dir::new("/sys/class/hwmon/", &["hwmon"])],
+        Tape => vec![Dir::new("/dev/", &["st"]).numbered()],
+        Nvme => vec![Dir::new("/dev/", &["nvme"]).numbered().whole()],
+        ScsiGeneric => vec![Dir::new("/dev/", &["sg"]).numbered()],
+        Loop => vec![Dir::new("/dev/", &["loop"]).numbered().block()],
+        Hwrng => vec![Dir::new("/dev/", &["hwrng"])],
+        Accelerator => {
+            vec![Dir::new("/dev/", &["accel", "kfd", "apex_"])
+                .within(&["accel"])]
+        }
+        Infiniband => {
+            vec![Dir::new("/dev/infiniband/", &["uverbs", "rdma_cm"])]
+        }
+        Vfio => vec![Dir::new("/dev/vfio/", &[""]).numbered()],
+        Tun => vec![Dir::new("/dev/net/", &["tun"])],
+        PowerDevice => {
+            vec![Dir::new("/dev/", &["hidraw"]).usage_pages(&[0x84, 0x85])]
+        }
+        Braille => vec![Dir::new("/dev/", &["hidraw"]).usage_pages(&[0x41])],
+    }
+}
@@ -0,0 +1,83 @@
+use std::{fmt, io};
+
+use crate::Found;
+
+/// An error connecting to a [`Found`] device.
+///
+/// Keeps the OS error, so a device that needs permissions (like a udev rule)
+/// can be told apart from one that's busy or already gone, and gives the
+/// [`Found`] device back to retry with.
+///
+/// ```rust, no_run
+/// use std::io::ErrorKind;
+///
+/// use lookit::Searcher;
+/// use pasts::prelude::*;
+///
+/// #[async_main::async_main]
+/// async fn main(_spawner: impl async_main::Spawn) {
+///     let found = Searcher::with_camera().next().await;
+///
+///     match found.connect() {
+///         Ok((device, access)) => drop((device, access)),
+///         Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+///             eprintln!("{}: needs permission to connect", e.found().id());
+///         }
+///         Err(e) => eprintln!("{e}"),
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Error {
+    found: Found,
+    error: io::Error,
+}
+
+impl Error {
+    pub(crate) fn new(found: Found, error: io::Error) -> Self {
+        Self { found, error }
+    }
+
+    /// Get the device that couldn't be connected to.
+    pub fn found(&self) -> &Found {
+        &self.found
+    }
+
+    /// Get the reason the device couldn't be connected to.
+    pub fn io_error(&self) -> &io::Error {
+        &self.error
+    }
+
+    /// Get the kind of OS error.
+    pub fn kind(&self) -> io::ErrorKind {
+        self.error.kind()
+    }
+
+    /// Give the device back, to retry connecting.
+    pub fn into_found(self) -> Found {
+        self.found
+    }
+
+    /// Split into the device and the OS error.
+    pub fn into_parts(self) -> (Found, io::Error) {
+        (self.found, self.error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.found.path().display(), self.error)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        error.error
+    }
+}
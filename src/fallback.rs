@@ -0,0 +1,488 @@
+use std::{
+    collections::VecDeque,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+#[cfg(unix)]
+use std::{
+    fs::OpenOptions,
+    os::{
+        fd::{AsRawFd, BorrowedFd, OwnedFd},
+        raw::c_int,
+        unix::fs::OpenOptionsExt,
+    },
+};
+
+#[cfg(unix)]
+use smelling_salts::Watch;
+
+use crate::{
+    devfs,
+    scan::{self, Scanner},
+    Attempt, Backend, ConnectOptions, Device, DeviceInfo, Dir, Feature, Found,
+    Interface, Kind, Platform, Restriction, SearcherBuilder, Snapshot, Target,
+    UsbInfo, VideoInfo,
+};
+
+/// How often device directories get rescanned
+const INTERVAL: Duration = Duration::from_secs(1);
+
+#[cfg(target_os = "android")]
+const O_NONBLOCK: c_int = 0o4000;
+#[cfg(any(target_os = "illumos", target_os = "solaris"))]
+const O_NONBLOCK: c_int = 0x80;
+#[cfg(all(
+    unix,
+    not(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "solaris"
+    ))
+))]
+const O_NONBLOCK: c_int = 0x0004;
+
+#[cfg(target_os = "android")]
+const O_EXCL: c_int = 0o200;
+#[cfg(any(target_os = "illumos", target_os = "solaris"))]
+const O_EXCL: c_int = 0x400;
+#[cfg(all(
+    unix,
+    not(any(
+        target_os = "android",
+        target_os = "illumos",
+        target_os = "solaris"
+    ))
+))]
+const O_EXCL: c_int = 0x0800;
+
+#[cfg(unix)]
+const F_SETFD: c_int = 2;
+
+#[cfg(unix)]
+extern "C" {
+    fn fcntl(fd: c_int, cmd: c_int, ...) -> c_int;
+}
+
+impl Interface for Platform {
+    type Closed = pasts::notify::Pending<()>;
+    type Searcher = Scanner;
+
+    fn searcher(
+        builder: &SearcherBuilder,
+        attempts: &mut Vec<Attempt>,
+    ) -> Option<Scanner> {
+        if !builder.allows(Backend::Scan) {
+            return None;
+        }
+
+        let mut scanner =
+            Scanner::new(dirs(&builder.target), INTERVAL, attempts)?;
+
+        if !builder.initial {
+            scanner.skip_initial();
+        }
+
+        Some(scanner)
+    }
+
+    fn backend(_searcher: &Scanner) -> Backend {
+        Backend::Scan
+    }
+
+    fn restrictions(_attempts: &[Attempt]) -> Vec<Restriction> {
+        let mut restrictions = Vec::new();
+
+        if cfg!(unix) && fs::read_dir("/dev").is_err() {
+            restrictions.push(Restriction::Devices);
+        }
+
+        restrictions
+    }
+
+    #[cfg(unix)]
+    fn open(
+        found: Found,
+        options: &ConnectOptions,
+    ) -> Result<Device, (Found, io::Error)> {
+        use crate::Events::*;
+        let watch = match options.events {
+            Read() => Watch::INPUT,
+            Write() => Watch::OUTPUT,
+            All() => Watch::INPUT.output(),
+        };
+
+        Ok(Device::new(Self::open_fd(found, options)?, watch))
+    }
+
+    #[cfg(unix)]
+    fn open_fd(
+        found: Found,
+        options: &ConnectOptions,
+    ) -> Result<OwnedFd, (Found, io::Error)> {
+        use crate::Events::*;
+        let (read, write) = match options.events {
+            Read() => (true, false),
+            Write() => (false, true),
+            All() => (true, true),
+        };
+        let mut flags = options.flags;
+
+        if options.nonblocking {
+            flags |= O_NONBLOCK;
+        }
+
+        if options.exclusive {
+            flags |= O_EXCL;
+        }
+
+        let fd = match OpenOptions::new()
+            .read(read)
+            .write(write)
+            .append(write && options.append)
+            .custom_flags(flags)
+            .open(&found.path)
+        {
+            Ok(file) => OwnedFd::from(file),
+            Err(error) => return Err((found, error)),
+        };
+
+        // Files are always opened with `O_CLOEXEC`
+        if !options.cloexec
+            && unsafe { fcntl(fd.as_raw_fd(), F_SETFD, 0) } == -1
+        {
+            return Err((found, io::Error::last_os_error()));
+        }
+
+        Ok(fd)
+    }
+
+    #[cfg(not(unix))]
+    fn open(
+        found: Found,
+        _options: &ConnectOptions,
+    ) -> Result<Device, (Found, io::Error)> {
+        let error = io::Error::new(
+            io::ErrorKind::Unsupported,
+            "can't connect to devices on this platform",
+        );
+
+        Err((found, error))
+    }
+
+    fn parent(_path: &Path) -> Option<String> {
+        None
+    }
+
+    fn id(_path: &Path) -> Option<String> {
+        None
+    }
+
+    fn stable_path(_path: &Path) -> Option<PathBuf> {
+        None
+    }
+
+    fn settled(_path: &Path) -> bool {
+        true
+    }
+
+    fn port(_path: &Path) -> Option<String> {
+        None
+    }
+
+    fn name(_path: &Path) -> Option<String> {
+        None
+    }
+
+    fn partition(_path: &Path) -> bool {
+        false
+    }
+
+    fn index(_path: &Path) -> Option<u32> {
+        None
+    }
+
+    fn usb_class(_path: &Path, _class: u8) -> bool {
+        false
+    }
+
+    fn usb_vendor(_path: &Path) -> Option<u16> {
+        None
+    }
+
+    fn usb_info(_path: &Path) -> Option<UsbInfo> {
+        None
+    }
+
+    fn device_info(_path: &Path) -> DeviceInfo {
+        DeviceInfo::default()
+    }
+
+    fn hid_usage(_path: &Path, _pages: &[u16]) -> bool {
+        false
+    }
+
+    fn supports(_path: &Path, _feature: Feature) -> bool {
+        false
+    }
+
+    fn video_info(_path: &Path) -> io::Result<VideoInfo> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    #[cfg(unix)]
+    fn fd(_searcher: &Scanner) -> Option<BorrowedFd<'_>> {
+        None
+    }
+
+    fn take_removed(searcher: &mut Scanner) -> VecDeque<PathBuf> {
+        searcher.take_removed()
+    }
+
+    fn watch_close(_path: &Path) -> Option<pasts::notify::Pending<()>> {
+        None
+    }
+
+    fn watch_removal(_path: &Path) -> Option<pasts::notify::Pending<()>> {
+        None
+    }
+
+    fn enumerate(kind: Kind) -> Vec<PathBuf> {
+        scan::list(&dirs(&Target::Kind(kind))).into_iter().collect()
+    }
+
+    fn save(searcher: Scanner, snapshot: &mut Snapshot) {
+        searcher.save(snapshot);
+    }
+
+    unsafe fn restore(snapshot: &Snapshot) -> Option<Scanner> {
+        let dirs = dirs(&snapshot.target);
+
+        if dirs.is_empty() {
+            return None;
+        }
+
+        Some(Scanner::restore(dirs, INTERVAL, snapshot))
+    }
+}
+
+/// Directories where device nodes for a target are found
+fn dirs(target: &Target) -> Vec<Dir> {
+    match target {
+        Target::Custom(dir) => vec![dir.clone()],
+        Target::Kind(kind) if devfs::SYSFS_ONLY.contains(kind) => Vec::new(),
+        Target::Kind(kind) => kind_dirs(*kind),
+    }
+}
+
+/// Directories where device nodes for a kind are found
+#[cfg(target_os = "android")]
+fn kind_dirs(kind: Kind) -> Vec<Dir> {
+    devfs::dirs(kind)
+}
+
+/// Directories where device nodes for a kind are found
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+fn kind_dirs(kind: Kind) -> Vec<Dir> {
+    use Kind::*;
+
+    match kind {
+        Input => vec![Dir::new("/dev/input/", &["event"])],
+        Audio => vec![Dir::new("/dev/", &["dsp"])],
+        Midi => vec![Dir::new("/dev/", &["umidi", "midi"])],
+        Camera => vec![Dir::new("/dev/", &["video"])],
+        Serial => vec![Dir::new("/dev/", &["cuaU"])],
+        Usb => vec![Dir::new("/dev/", &["ugen"])],
+        Hid => vec![Dir::new("/dev/", &["hidraw", "uhid"])],
+        // Bluetooth adapters don't appear as nodes in a directory
+        Bluetooth => Vec::new(),
+        Joystick => vec![Dir::new("/dev/", &["joy"])],
+        Storage => {
+            vec![Dir::new("/dev/", &["da", "ada", "nvd", "nda", "mmcsd"])]
+        }
+        SdCard => vec![Dir::new("/dev/", &["mmcsd"])],
+        // Network interfaces don't appear as nodes in a directory
+        Network => Vec::new(),
+        Gpu => vec![Dir::new("/dev/dri/", &["card", "renderD"])],
+        Printer => vec![Dir::new("/dev/", &["ulpt", "lpt"])],
+        Tuner => {
+            vec![Dir::new("/dev/dvb/", &["frontend", "demux"]).recursive()]
+        }
+        // HDMI CEC adapters aren't supported on this platform
+        Cec => Vec::new(),
+        // IIO sensors aren't supported on this platform
+        Sensor => Vec::new(),
+        Gpio => vec![Dir::new("/dev/", &["gpioc"])],
+        I2c => vec![Dir::new("/dev/", &["iic"])],
+        Spi => vec![Dir::new("/dev/", &["spigen"])],
+        // CAN interfaces don't appear as nodes in a directory
+        Can => Vec::new(),
+        Tpm => vec![Dir::new("/dev/", &["tpm"])],
+        // Watchdogs don't appear as nodes in a directory
+        Watchdog => Vec::new(),
+        // NFC devices don't appear as nodes in a directory
+        Nfc => Vec::new(),
+        // Real-time clocks don't appear as nodes in a directory
+        Rtc => Vec::new(),
+        // Framebuffers don't appear as nodes in a directory
+        Framebuffer => Vec::new(),
+        // Media controllers aren't supported on this platform
+        Media => Vec::new(),
+        // V4L2 subdevices aren't supported on this platform
+        Subdev => Vec::new(),
+        // Radio tuners aren't supported on this platform
+        Radio => Vec::new(),
+        // IR receivers aren't supported on this platform
+        Lirc => Vec::new(),
+        SoundCard => vec![Dir::new("/dev/", &["mixer"])],
+        // ALSA devices aren't supported on this platform
+        Hwdep => Vec::new(),
+        // ALSA devices aren't supported on this platform
+        Compress => Vec::new(),
+        Uinput => vec![Dir::new("/dev/", &["uinput"]).writable()],
+        // Power supplies don't appear as nodes in a directory
+        PowerSupply => Vec::new(),
+        // Hardware monitors don't appear as nodes in a directory
+        Hwmon => Vec::new(),
+        Tape => vec![Dir::new("/dev/", &["sa"]).numbered()],
+        Nvme => vec![Dir::new("/dev/", &["nvme"]).numbered()],
+        ScsiGeneric => vec![Dir::new("/dev/", &["pass"]).numbered()],
+        Loop => vec![Dir::new("/dev/", &["md"]).numbered()],
+        // Hardware RNGs don't appear as nodes in a directory
+        Hwrng => Vec::new(),
+        // AI accelerators aren't supported on this platform
+        Accelerator => Vec::new(),
+        Infiniband => vec![Dir::new("/dev/", &["uverbs", "rdma_cm"])],
+        // VFIO isn't supported on this platform
+        Vfio => Vec::new(),
+        // TUN/TAP devices are created on demand on this platform
+        Tun => Vec::new(),
+        // Filtered out by `dirs()`
+        Display | Scanner | Smartcard | Fingerprint | PowerDevice | Braille => {
+            Vec::new()
+        }
+    }
+}
+
+/// Directories where device nodes for a kind are found
+#[cfg(any(target_os = "openbsd", target_os = "netbsd"))]
+fn kind_dirs(kind: Kind) -> Vec<Dir> {
+    use Kind::*;
+
+    match kind {
+        Input => vec![Dir::new("/dev/", &["uhid", "ujoy"])],
+        Audio => vec![Dir::new("/dev/", &["audio"])],
+        Midi => vec![Dir::new("/dev/", &["rmidi"])],
+        Camera => vec![Dir::new("/dev/", &["video"])],
+        #[cfg(target_os = "openbsd")]
+        Serial => vec![Dir::new("/dev/", &["cuaU"])],
+        #[cfg(target_os = "netbsd")]
+        Serial => vec![Dir::new("/dev/", &["dtyU"])],
+        Usb => vec![Dir::new("/dev/", &["ugen"])],
+        Hid => vec![Dir::new("/dev/", &["uhid"])],
+        // Bluetooth adapters don't appear as nodes in a directory
+        Bluetooth => Vec::new(),
+        Joystick => vec![Dir::new("/dev/", &["ujoy"])],
+        Storage => vec![Dir::new("/dev/", &["sd", "wd", "ld"]).block()],
+        // SD cards show up as regular disks
+        SdCard => Vec::new(),
+        // Network interfaces don't appear as nodes in a directory
+        Network => Vec::new(),
+        Gpu => vec![Dir::new("/dev/dri/", &["card", "renderD"])],
+        Printer => vec![Dir::new("/dev/", &["ulpt", "lpt"])],
+        // DVB tuners aren't supported on this platform
+        Tuner => Vec::new(),
+        // HDMI CEC adapters aren't supported on this platform
+        Cec => Vec::new(),
+        // IIO sensors aren't supported on this platform
+        Sensor => Vec::new(),
+        Gpio => vec![Dir::new("/dev/", &["gpio"])],
+        I2c => vec![Dir::new("/dev/", &["iic"])],
+        // SPI devices aren't accessible on this platform
+        Spi => Vec::new(),
+        // CAN interfaces don't appear as nodes in a directory
+        Can => Vec::new(),
+        // TPMs aren't accessible on this platform
+        Tpm => Vec::new(),
+        // Watchdogs don't appear as nodes in a directory
+        Watchdog => Vec::new(),
+        // NFC devices don't appear as nodes in a directory
+        Nfc => Vec::new(),
+        // Real-time clocks don't appear as nodes in a directory
+        Rtc => Vec::new(),
+        // Framebuffers don't appear as nodes in a directory
+        Framebuffer => Vec::new(),
+        // Media controllers aren't supported on this platform
+        Media => Vec::new(),
+        // V4L2 subdevices aren't supported on this platform
+        Subdev => Vec::new(),
+        // Radio tuners aren't supported on this platform
+        Radio => Vec::new(),
+        // IR receivers aren't supported on this platform
+        Lirc => Vec::new(),
+        SoundCard => vec![Dir::new("/dev/", &["mixer"])],
+        // ALSA devices aren't supported on this platform
+        Hwdep => Vec::new(),
+        // ALSA devices aren't supported on this platform
+        Compress => Vec::new(),
+        // Virtual input devices aren't supported on this platform
+        Uinput => Vec::new(),
+        // Power supplies don't appear as nodes in a directory
+        PowerSupply => Vec::new(),
+        // Hardware monitors don't appear as nodes in a directory
+        Hwmon => Vec::new(),
+        Tape => vec![Dir::new("/dev/", &["rst"]).numbered()],
+        // NVMe devices show up as regular disks
+        Nvme => Vec::new(),
+        // SCSI generic devices aren't supported on this platform
+        ScsiGeneric => Vec::new(),
+        Loop => vec![Dir::new("/dev/", &["vnd"]).numbered().block()],
+        // Hardware RNGs don't appear as nodes in a directory
+        Hwrng => Vec::new(),
+        // AI accelerators aren't supported on this platform
+        Accelerator => Vec::new(),
+        // RDMA devices aren't supported on this platform
+        Infiniband => Vec::new(),
+        // VFIO isn't supported on this platform
+        Vfio => Vec::new(),
+        // TUN/TAP devices are created on demand on this platform
+        Tun => Vec::new(),
+        // Filtered out by `dirs()`
+        Display | Scanner | Smartcard | Fingerprint | PowerDevice | Braille => {
+            Vec::new()
+        }
+    }
+}
+
+/// Directories where device nodes for a kind are found
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn kind_dirs(kind: Kind) -> Vec<Dir> {
+    use Kind::*;
+
+    match kind {
+        Serial => vec![Dir::new("/dev/", &["cu.usbserial", "cu.usbmodem"])],
+        Storage => vec![Dir::new("/dev/", &["disk"]).block()],
+        // SD cards show up as regular disks
+        SdCard => Vec::new(),
+        // Network interfaces don't appear as nodes in a directory
+        Network => Vec::new(),
+        // Other devices don't appear as nodes in a directory
+        _ => Vec::new(),
+    }
+}
+
+/// Directories where device nodes for a kind are found
+///
+/// Devices on this platform don't appear as nodes in a directory.
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd",
+    target_os = "netbsd",
+    target_os = "macos",
+    target_os = "ios",
+)))]
+fn kind_dirs(_kind: Kind) -> Vec<Dir> {
+    Vec::new()
+}
@@ -0,0 +1,61 @@
+use std::{borrow::Cow, fmt, path::Path, sync::Arc};
+
+use crate::{pattern, Feature, Found, Interface, Platform};
+
+type Predicate = Arc<dyn Fn(&Found) -> bool + Send + Sync>;
+
+/// Checks a device must pass to be delivered by a searcher
+#[derive(Clone, Default)]
+pub(crate) struct Filters {
+    pub(crate) features: Vec<Feature>,
+    pub(crate) patterns: Vec<Cow<'static, str>>,
+    /// USB vendor ID the device must have
+    pub(crate) vendor: Option<u16>,
+    /// USB product ID the device must have
+    pub(crate) product: Option<u16>,
+    pub(crate) predicates: Vec<Predicate>,
+}
+
+impl fmt::Debug for Filters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filters")
+            .field("features", &self.features)
+            .field("patterns", &self.patterns)
+            .field("vendor", &self.vendor)
+            .field("product", &self.product)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Filters {
+    /// Check if a device has the USB vendor and product IDs required
+    fn usb_matches(&self, path: &Path) -> bool {
+        if self.vendor.is_none() && self.product.is_none() {
+            return true;
+        }
+
+        Platform::usb_info(path).map_or(false, |info| {
+            self.vendor.map_or(true, |vendor| info.vendor == vendor)
+                && self.product.map_or(true, |product| info.product == product)
+        })
+    }
+
+    /// Check if a found device passes every filter
+    pub(crate) fn accepts(&self, found: &Found) -> bool {
+        let path = found.path();
+        let text = path.to_string_lossy();
+        let name = text.rsplit('/').next().unwrap_or(&text);
+
+        // Cheapest checks first, since checking features opens the device
+        self.patterns.iter().all(|pattern| {
+            let text = if pattern.contains('/') { &text } else { name };
+
+            pattern::matches(pattern, text)
+        }) && self.usb_matches(path)
+            && self.predicates.iter().all(|predicate| predicate(found))
+            && self
+                .features
+                .iter()
+                .all(|feature| Platform::supports(path, *feature))
+    }
+}
@@ -0,0 +1,205 @@
+use crate::Backend;
+
+/// How a [`Searcher`](crate::Searcher) delivers [`Found`](crate::Found)
+/// devices, depending on its [`Backend`].
+///
+/// Devices already there when searching starts are always found.
+///
+/// ```rust
+/// use lookit::{Backend, DeliveryGuarantees};
+///
+/// let minimum = DeliveryGuarantees::MINIMUM;
+///
+/// for backend in [
+///     Backend::Inotify,
+///     Backend::Uevent,
+///     Backend::Rtnetlink,
+///     Backend::Scan,
+/// ] {
+///     let guarantees = backend.guarantees();
+///
+///     assert!(minimum.duplicates || !guarantees.duplicates);
+///     assert!(minimum.missed_replug || !guarantees.missed_replug);
+///     assert!(!minimum.ordered || guarantees.ordered);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct DeliveryGuarantees {
+    /// A device may be found again while it's still connected
+    /// (at-least-once, rather than exactly-once), like when it's added right
+    /// as searching starts and found by both the initial search and the
+    /// backend, or when its permissions change
+    pub duplicates: bool,
+    /// A device removed and added again between rescans may not be found
+    /// again
+    pub missed_replug: bool,
+    /// Devices added while searching are found in the order they were added
+    pub ordered: bool,
+}
+
+impl DeliveryGuarantees {
+    /// Guarantees that hold whichever backend is used.
+    pub const MINIMUM: Self = Self {
+        duplicates: true,
+        missed_replug: true,
+        ordered: false,
+    };
+}
+
+impl Backend {
+    /// Get the guarantees of how devices are delivered with this backend.
+    pub const fn guarantees(self) -> DeliveryGuarantees {
+        match self {
+            // Watching starts before the initial search, so nothing is missed,
+            // but permission changes find device files again at any time, and
+            // ones that can't be opened yet are held back while later ones
+            // are found
+            Self::Inotify => DeliveryGuarantees {
+                duplicates: true,
+                missed_replug: false,
+                ordered: false,
+            },
+            // Watching starts before the initial search, so nothing is missed
+            Self::Uevent | Self::Rtnetlink => DeliveryGuarantees {
+                duplicates: true,
+                missed_replug: false,
+                ordered: true,
+            },
+            // Devices seen on the last scan are remembered, and each scan
+            // finds new devices in directory order
+            Self::Scan => DeliveryGuarantees {
+                duplicates: false,
+                missed_replug: true,
+                ordered: false,
+            },
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::{
+        fs, os::unix::fs::PermissionsExt, path::PathBuf, thread, time::Duration,
+    };
+
+    use super::*;
+    use crate::{Searcher, SearcherBuilder};
+
+    /// Create an empty directory to search, with a device file in it
+    fn dir(name: &str) -> (String, PathBuf) {
+        let dir = std::env::temp_dir()
+            .join(format!("lookit-guarantees-{name}-{}", std::process::id(),));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("dev0"), "").unwrap();
+
+        (format!("{}/", dir.display()), dir)
+    }
+
+    /// Start searching a directory with one backend
+    ///
+    /// Only driven with `try_next()`, so it's left unwatched: wakes that are
+    /// never received would stall smelling_salts' reactor thread for the
+    /// other tests.
+    fn searcher(path: String, backend: Backend) -> Searcher {
+        let searcher = SearcherBuilder::with_path(path, ["dev"])
+            .backends(&[backend])
+            .watched(false)
+            .build();
+
+        assert_eq!(searcher.backend(), Some(backend));
+        searcher
+    }
+
+    /// Get the paths of the devices found so far
+    fn found(searcher: &mut Searcher) -> Vec<PathBuf> {
+        std::iter::from_fn(|| searcher.try_next())
+            .map(|found| found.path().to_path_buf())
+            .collect()
+    }
+
+    /// Change a file's permissions, like udev does for new device files
+    fn chmod(path: &PathBuf, mode: u32) {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    /// Wait for a scanner to rescan
+    fn rescan() {
+        thread::sleep(Duration::from_millis(1500));
+    }
+
+    #[test]
+    fn inotify_duplicates() {
+        let (path, dir) = dir("inotify-duplicates");
+        let mut searcher = searcher(path, Backend::Inotify);
+        let node = dir.join("dev0");
+
+        assert_eq!(found(&mut searcher), [node.as_path()]);
+        chmod(&node, 0o600);
+        assert_eq!(found(&mut searcher), [node.as_path()]);
+        assert!(Backend::Inotify.guarantees().duplicates);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn inotify_replug() {
+        let (path, dir) = dir("inotify-replug");
+        let mut searcher = searcher(path, Backend::Inotify);
+        let node = dir.join("dev0");
+
+        assert_eq!(found(&mut searcher), [node.as_path()]);
+        fs::remove_file(&node).unwrap();
+        fs::write(&node, "").unwrap();
+        assert_eq!(found(&mut searcher), [node.as_path()]);
+        assert!(!Backend::Inotify.guarantees().missed_replug);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn inotify_debounced_replug() {
+        const WINDOW: Duration = Duration::from_millis(300);
+
+        let (path, dir) = dir("inotify-debounced-replug");
+        let mut searcher = searcher(path, Backend::Inotify).debounce(WINDOW);
+        let node = dir.join("dev0");
+
+        // Settling, so the replug is coalesced with it
+        assert!(found(&mut searcher).is_empty());
+        fs::remove_file(&node).unwrap();
+        fs::write(&node, "").unwrap();
+        assert!(found(&mut searcher).is_empty());
+        thread::sleep(WINDOW);
+        assert_eq!(found(&mut searcher), [node.as_path()]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn scan_duplicates() {
+        let (path, dir) = dir("scan-duplicates");
+        let mut searcher = searcher(path, Backend::Scan);
+        let node = dir.join("dev0");
+
+        assert_eq!(found(&mut searcher), [node.as_path()]);
+        chmod(&node, 0o600);
+        rescan();
+        assert!(found(&mut searcher).is_empty());
+        assert!(!Backend::Scan.guarantees().duplicates);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn scan_replug() {
+        let (path, dir) = dir("scan-replug");
+        let mut searcher = searcher(path, Backend::Scan);
+        let node = dir.join("dev0");
+
+        assert_eq!(found(&mut searcher), [node.as_path()]);
+        fs::remove_file(&node).unwrap();
+        fs::write(&node, "").unwrap();
+        rescan();
+        assert!(found(&mut searcher).is_empty());
+        assert!(Backend::Scan.guarantees().missed_replug);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
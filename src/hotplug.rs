@@ -0,0 +1,150 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    fs, io, mem,
+    os::fd::OwnedFd,
+    path::{Path, PathBuf},
+};
+
+use pasts::prelude::*;
+
+use super::{listener::Listener, uevent::Uevent};
+
+/// Directory GPUs and their connectors are found in
+const CLASS: &str = "/sys/class/drm/";
+
+/// Get the paths of the GPU connectors with a display connected
+pub(super) fn connected() -> HashSet<PathBuf> {
+    fs::read_dir(CLASS)
+        .into_iter()
+        .flatten()
+        .flatten()
+        // Connectors are named after their GPU, like "card0-HDMI-A-1"
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            name.starts_with("card") && name.contains('-')
+        })
+        .map(|entry| entry.path())
+        .filter(|path| {
+            fs::read_to_string(path.join("status"))
+                .map_or(false, |status| status.trim() == "connected")
+        })
+        .collect()
+}
+
+/// Listens for displays being connected, from DRM hotplug uevents
+#[derive(Debug)]
+pub(super) struct Hotplug {
+    uevent: Uevent,
+    /// Paths of connectors that had a display connected last time checked
+    connected: HashSet<PathBuf>,
+    /// Paths of connectors a display was connected to, not yet returned
+    queue: VecDeque<PathBuf>,
+    /// Paths of connectors a display was disconnected from, not yet taken
+    removed: VecDeque<PathBuf>,
+}
+
+impl Hotplug {
+    pub(super) fn new(watched: bool) -> io::Result<Self> {
+        let uevent = Uevent::new(watched)?;
+        let connected = HashSet::new();
+        let queue = VecDeque::new();
+        let removed = VecDeque::new();
+        let mut hotplug = Self {
+            uevent,
+            connected,
+            queue,
+            removed,
+        };
+
+        // Displays that are already connected are found first
+        hotplug.rescan();
+
+        Ok(hotplug)
+    }
+
+    /// Listen on a socket bound before an `exec()`, with the connectors that
+    /// had a display connected then
+    ///
+    /// Displays connected or disconnected since then are found, even if their
+    /// uevents were lost while the socket wasn't being read.
+    pub(super) fn restore(fd: OwnedFd, connected: HashSet<PathBuf>) -> Self {
+        let uevent = Uevent::with_fd(fd, true);
+        let queue = VecDeque::new();
+        let removed = VecDeque::new();
+        let mut hotplug = Self {
+            uevent,
+            connected,
+            queue,
+            removed,
+        };
+
+        hotplug.rescan();
+        hotplug
+    }
+
+    /// Skip the displays that are already connected
+    pub(super) fn skip_initial(&mut self) {
+        self.queue.clear();
+    }
+
+    /// Get the socket
+    pub(super) fn device(&self) -> &Listener {
+        self.uevent.device()
+    }
+
+    /// Get the socket, paths not yet returned, and the connectors with a
+    /// display connected
+    pub(super) fn into_parts(
+        self,
+    ) -> (Listener, VecDeque<PathBuf>, HashSet<PathBuf>) {
+        (self.uevent.into_parts().0, self.queue, self.connected)
+    }
+
+    /// Take the paths of connectors a display was disconnected from since
+    /// last time
+    pub(super) fn take_removed(&mut self) -> VecDeque<PathBuf> {
+        mem::take(&mut self.removed)
+    }
+
+    /// Check which connectors had a display connected or disconnected since
+    /// last time
+    fn rescan(&mut self) {
+        let connected = connected();
+        let mut added: Vec<_> =
+            connected.difference(&self.connected).cloned().collect();
+        let mut removed: Vec<_> =
+            self.connected.difference(&connected).cloned().collect();
+
+        added.sort();
+        removed.sort();
+        self.queue.extend(added);
+        self.removed.extend(removed);
+        self.connected = connected;
+    }
+
+    /// Get the path of the next connector a display was connected to
+    pub(super) fn poll_added(&mut self, task: &mut Task<'_>) -> Poll<PathBuf> {
+        loop {
+            if let Some(path) = self.queue.pop_front() {
+                return Ready(path);
+            }
+
+            let added = self.uevent.poll_added(task);
+
+            // Connectors stay in sysfs, so devices removed aren't displays
+            // being disconnected
+            self.uevent.take_removed().clear();
+
+            let Ready(path) = added else {
+                return Pending;
+            };
+
+            // Hotplug events don't say which connector changed
+            if path.starts_with(Path::new(CLASS)) {
+                self.rescan();
+            }
+        }
+    }
+}
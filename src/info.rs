@@ -0,0 +1,70 @@
+/// USB metadata for a [`Found`](crate::Found) device, from
+/// [`Found::info()`](crate::Found::info).
+///
+/// This identifies the physical device, so it can be used to filter devices or
+/// to save settings for each one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct UsbInfo {
+    /// USB vendor ID
+    pub vendor: u16,
+    /// USB product ID
+    pub product: u16,
+    /// Serial number, if the device has one
+    pub serial: Option<String>,
+}
+
+/// Kernel metadata for a [`Found`](crate::Found) device, from
+/// [`Found::device_info()`](crate::Found::device_info).
+///
+/// Fields are `None` when they don't apply to the device, or can't be read
+/// (which is always the case on platforms other than Linux).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct DeviceInfo {
+    /// Bus the device is attached to (like `usb`, `pci` or `hid`)
+    pub bus: Option<String>,
+    /// Kernel driver bound to the device
+    pub driver: Option<String>,
+    /// Major and minor device number of the device node
+    pub devnum: Option<(u32, u32)>,
+    /// Subsystem the device node belongs to (like `input`, `sound` or
+    /// `video4linux`)
+    pub subsystem: Option<String>,
+}
+
+/// Video capture metadata for a camera, from
+/// [`Camera::info()`](crate::Camera::info).
+///
+/// Read from the V4L2 driver, so the format is the one the camera was left set
+/// to capture in.  A capture library may negotiate a different one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct VideoInfo {
+    /// Kernel driver of the camera (like `uvcvideo`)
+    pub driver: String,
+    /// Name of the camera
+    pub card: String,
+    /// `V4L2_CAP_*` capability flags of the device node
+    pub capabilities: u32,
+    /// Format frames are captured in
+    pub format: VideoFormat,
+}
+
+/// Format of the frames a camera captures, part of [`VideoInfo`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct VideoFormat {
+    /// Width of a frame, in pixels
+    pub width: u32,
+    /// Height of a frame, in pixels
+    pub height: u32,
+    /// Pixel format (like `*b"YUYV"` or `*b"MJPG"`)
+    pub fourcc: [u8; 4],
+    /// Bytes between the start of each line of the first plane
+    pub bytes_per_line: u32,
+    /// Bytes in a frame, of all planes
+    pub size_image: u32,
+    /// Number of planes, more than 1 only for multiplanar formats
+    pub planes: u8,
+}
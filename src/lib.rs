@@ -1,7 +1,12 @@
 //! The "Lookit!" crate checks for new devices in a cross-platform asynchronous
 //! manner.  Returns the `RawFd` equivalent for the target platform.
 //!
-//!  - Linux: inotify on /dev/*
+//!  - Linux: inotify on /dev/*, falling back to netlink uevents, then periodic
+//!    scans of /dev/*
+//!  - WASI: periodic scan of /dev/*
+//!  - Android, BSDs: periodic scan of /dev/*
+//!  - MacOS, iOS: periodic scan of /dev/cu.* (serial ports) and /dev/disk*
+//!    (storage)
 //!  - Web: JavaScript event listeners
 //!  - Others: TODO
 //!
@@ -20,24 +25,205 @@
 //! }
 //! ```
 //!
+//! ## Other Executors
+//! Searchers implement pasts' [`Notify`], which is polled with a plain
+//! [`Context`](std::task::Context), so they work with any executor.  With the
+//! `stream` feature, `Searcher::into_stream()` turns one into a
+//! `futures_core::Stream`:
+//!
+//! ```rust, ignore
+//! use futures::StreamExt;
+//! use lookit::Searcher;
+//!
+//! async fn search() {
+//!     let mut cameras = Searcher::with_camera().into_stream();
+//!
+//!     while let Some(found) = cameras.next().await {
+//!         println!("Found {found:?}");
+//!     }
+//! }
+//! ```
+//!
+//! Executors with their own reactor can watch the searcher's file descriptor
+//! instead of waiting for smelling_salts to wake them.  With the `tokio`
+//! feature, `SearcherBuilder::build_tokio()` registers it with tokio's
+//! `AsyncFd`, and with the `async-io` feature (for smol and async-std),
+//! `SearcherBuilder::build_async_io()` registers it with async-io's `Async`.
+//! With the `mio` feature, `SearcherBuilder::build_mio()` gives a searcher
+//! that's a mio `Source`, for event loops without an async runtime.  For other
+//! reactors, build the searcher with [`SearcherBuilder::watched()`] turned off
+//! and register [`Searcher::as_fd()`].
+//!
 //! ## Implementation
+//! With inotify, device files that can't be opened yet when they're created
+//! are found once udev changes their permissions (or after a second, if it
+//! doesn't), so they can be connected to right away.
+//!
 //! Input
 //!  - inotify => /dev/input/event*
 //!  - `window.addEventListener("gamepadconnected", function(e) { });`
 //!
+//! Joystick
+//!  - inotify => /dev/input/js*
+//!
 //! Audio
 //!  - inotify => /dev/snd/pcm*
 //!  - `navigator.mediaDevices.getUserMedia(constraints).then(function(s) {
 //!    }).catch(function(denied_err) {})` // only one speakers connection ever
 //!
 //! MIDI
-//!  - inotify => /dev/snd/midi*, if no /dev/snd then /dev/midi*
+//!  - inotify => /dev/snd/midi*, /dev/snd/seq, if no /dev/snd then /dev/midi*
 //!  - <https://developer.mozilla.org/en-US/docs/Web/API/MIDIAccess>
 //!
 //! Camera
 //!  - inotify => /dev/video*
 //!  - `navigator.mediaDevices.getUserMedia(constraints).then(function(s) {
 //!    }).catch(function(denied_err) {})`
+//!
+//! Serial
+//!  - inotify => /dev/ttyUSB*, /dev/ttyACM*, /dev/rfcomm*
+//!  - <https://developer.mozilla.org/en-US/docs/Web/API/Serial>
+//!
+//! USB
+//!  - inotify => /dev/bus/usb/*/*
+//!  - <https://developer.mozilla.org/en-US/docs/Web/API/USB>
+//!
+//! HID
+//!  - inotify => /dev/hidraw*
+//!  - <https://developer.mozilla.org/en-US/docs/Web/API/WebHID_API>
+//!
+//! Bluetooth
+//!  - uevents => /sys/class/bluetooth/hci*
+//!  - <https://developer.mozilla.org/en-US/docs/Web/API/Web_Bluetooth_API>
+//!
+//! Storage
+//!  - inotify => /dev/sd*, /dev/nvme\*n\*, /dev/mmcblk* (block devices only)
+//!
+//! SD Card
+//!  - inotify => /dev/mmcblk* (whole disks only)
+//!
+//! Network
+//!  - rtnetlink => /sys/class/net/*
+//!
+//! GPU
+//!  - inotify => /dev/dri/card*, /dev/dri/renderD*
+//!  - <https://developer.mozilla.org/en-US/docs/Web/API/WebGPU_API>
+//!
+//! Display
+//!  - uevents => /sys/class/drm/card\*-\* (connected)
+//!
+//! Printer
+//!  - inotify => /dev/lp*, including /dev/usb/lp* (even if /dev/usb/ appears
+//!    later)
+//!
+//! Scanner
+//!  - inotify => /dev/bus/usb/*/* (still image interface class only)
+//!
+//! Tuner
+//!  - inotify => /dev/dvb/adapter*/frontend*, /dev/dvb/adapter*/demux*
+//!
+//! CEC
+//!  - inotify => /dev/cec*
+//!
+//! Sensors
+//!  - inotify => /dev/iio:device*
+//!  - <https://developer.mozilla.org/en-US/docs/Web/API/Sensor_APIs>
+//!
+//! GPIO
+//!  - inotify => /dev/gpiochip*
+//!
+//! I2C
+//!  - inotify => /dev/i2c-*
+//!
+//! SPI
+//!  - inotify => /dev/spidev*
+//!
+//! CAN
+//!  - rtnetlink => /sys/class/net/can*, /sys/class/net/vcan*
+//!
+//! TPM
+//!  - inotify => /dev/tpm*, /dev/tpmrm*
+//!
+//! Watchdog
+//!  - inotify => /dev/watchdog*
+//!
+//! Smartcard
+//!  - inotify => /dev/bus/usb/*/* (smart card interface class only)
+//!
+//! NFC
+//!  - uevents => /sys/class/nfc/nfc*
+//!
+//! RTC
+//!  - inotify => /dev/rtc*
+//!
+//! Framebuffer
+//!  - inotify => /dev/fb*
+//!
+//! Media Controller
+//!  - inotify => /dev/media*
+//!
+//! V4L2 Subdevice
+//!  - inotify => /dev/v4l-subdev*
+//!
+//! Radio
+//!  - inotify => /dev/radio*
+//!
+//! IR
+//!  - inotify => /dev/lirc*
+//!
+//! Sound Card
+//!  - inotify => /dev/snd/controlC*
+//!
+//! Sound Hardware Dependent
+//!  - inotify => /dev/snd/hwC\*D\*
+//!
+//! Sound Compressed Offload
+//!  - inotify => /dev/snd/comprC\*D\*
+//!
+//! Uinput
+//!  - inotify => /dev/uinput (writable only)
+//!
+//! Fingerprint
+//!  - inotify => /dev/bus/usb/*/* (fingerprint reader vendors only)
+//!
+//! Power Supply
+//!  - uevents => /sys/class/power_supply/*
+//!
+//! Hardware Monitor
+//!  - uevents => /sys/class/hwmon/hwmon*
+//!
+//! Tape
+//!  - inotify => /dev/st*
+//!
+//! NVMe
+//!  - inotify => /dev/nvme\*, /dev/nvme\*n\* (not partitions)
+//!
+//! SCSI Generic
+//!  - inotify => /dev/sg*
+//!
+//! Loop
+//!  - inotify => /dev/loop* (block devices only)
+//!
+//! Hardware RNG
+//!  - inotify => /dev/hwrng
+//!
+//! Accelerator
+//!  - inotify => /dev/accel/accel*, /dev/kfd, /dev/apex_*
+//!
+//! InfiniBand
+//!  - inotify => /dev/infiniband/uverbs*, /dev/infiniband/rdma_cm
+//!
+//! VFIO
+//!  - inotify => /dev/vfio/* (groups only)
+//!
+//! TUN/TAP
+//!  - inotify => /dev/net/tun
+//!
+//! Power Device
+//!  - inotify => /dev/hidraw* (power device and battery usage pages only)
+//!
+//! Braille
+//!  - inotify => /dev/hidraw* (braille display usage page only)
 
 #![warn(
     anonymous_parameters,
@@ -55,45 +241,872 @@
     variant_size_differences
 )]
 
+mod alias;
+#[cfg(all(feature = "async-io", unix))]
+mod async_io;
+mod blocking;
+mod builder;
+mod camera;
+mod changes;
+#[cfg(not(target_os = "wasi"))]
+mod channel;
+mod composite;
+mod connect;
+mod debounce;
+mod devfs;
+mod error;
+mod filter;
+mod guarantees;
+mod info;
+mod limit;
+#[cfg(any(feature = "async_main", not(target_os = "wasi")))]
+mod mailbox;
+mod merge;
+#[cfg(all(feature = "mio", unix))]
+mod mio;
+#[cfg(feature = "async_main")]
+mod monitor;
+mod pattern;
 #[cfg_attr(target_os = "linux", path = "linux.rs")]
-#[cfg_attr(not(target_os = "linux"), path = "mock.rs")]
+#[cfg_attr(target_os = "wasi", path = "wasi.rs")]
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "wasi")),
+    path = "fallback.rs"
+)]
 mod platform;
+mod removal;
+mod revocable;
+mod scan;
+mod scope;
+#[cfg(feature = "serde")]
+mod serialize;
+mod snapshot;
+mod stable;
+mod status;
+#[cfg(feature = "stream")]
+mod stream;
+mod timer;
+#[cfg(all(feature = "tokio", unix))]
+mod tokio;
+mod waitlist;
 
-use std::{cell::Cell, fmt};
+#[cfg(any(unix, target_os = "wasi"))]
+use std::os::fd::{BorrowedFd, OwnedFd};
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    fmt, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use pasts::prelude::*;
 use smelling_salts::Device;
 
-/// Device kinds
-enum Kind {
-    Input(),
-    Audio(),
-    Midi(),
-    Camera(),
+#[cfg(all(feature = "async-io", unix))]
+pub use self::async_io::AsyncIoSearcher;
+#[cfg(not(target_os = "wasi"))]
+pub use self::channel::Receiver;
+#[cfg(all(feature = "mio", unix))]
+pub use self::mio::MioSearcher;
+#[cfg(feature = "async_main")]
+pub use self::monitor::{spawn_monitor, Monitor, Subscription};
+#[cfg(feature = "stream")]
+pub use self::stream::SearchStream;
+#[cfg(all(feature = "tokio", unix))]
+pub use self::tokio::TokioSearcher;
+pub use self::{
+    alias::Aliases,
+    blocking::BlockingIter,
+    builder::SearcherBuilder,
+    camera::Camera,
+    changes::{Changes, Event},
+    composite::{Batcher, Composite},
+    connect::ConnectOptions,
+    error::Error,
+    guarantees::DeliveryGuarantees,
+    info::{DeviceInfo, UsbInfo, VideoFormat, VideoInfo},
+    merge::Merged,
+    removal::{watch_removal, Removal},
+    revocable::{Revocable, Revocation},
+    scope::{scope, Scope},
+    snapshot::Snapshot,
+    status::{Attempt, Restriction, Status},
+    waitlist::Waitlist,
+};
+use self::{
+    debounce::Debounce,
+    filter::Filters,
+    limit::{Limit, Permit},
+    stable::Stable,
+};
+
+/// A kind of device to search for, see [`Searcher::new()`].
+///
+/// Each kind has a stable [name](Kind::name), for storing kinds in
+/// configuration files.
+///
+/// ```rust
+/// use lookit::{Kind, Searcher};
+///
+/// assert_eq!(Kind::from_name("camera"), Some(Kind::Camera));
+///
+/// let searchers: Vec<Searcher> =
+///     Kind::ALL.iter().copied().map(Searcher::new).collect();
+/// # drop(searchers);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Kind {
+    /// See [`Searcher::with_input()`]
+    Input,
+    /// See [`Searcher::with_audio()`]
+    Audio,
+    /// See [`Searcher::with_midi()`]
+    Midi,
+    /// See [`Searcher::with_camera()`]
+    Camera,
+    /// See [`Searcher::with_serial()`]
+    Serial,
+    /// See [`Searcher::with_usb()`]
+    Usb,
+    /// See [`Searcher::with_hid()`]
+    Hid,
+    /// See [`Searcher::with_bluetooth()`]
+    Bluetooth,
+    /// See [`Searcher::with_joystick()`]
+    Joystick,
+    /// See [`Searcher::with_storage()`]
+    Storage,
+    /// See [`Searcher::with_sd_card()`]
+    SdCard,
+    /// See [`Searcher::with_network()`]
+    Network,
+    /// See [`Searcher::with_gpu()`]
+    Gpu,
+    /// See [`Searcher::with_display()`]
+    Display,
+    /// See [`Searcher::with_printer()`]
+    Printer,
+    /// See [`Searcher::with_scanner()`]
+    Scanner,
+    /// See [`Searcher::with_tuner()`]
+    Tuner,
+    /// See [`Searcher::with_cec()`]
+    Cec,
+    /// See [`Searcher::with_sensors()`]
+    Sensor,
+    /// See [`Searcher::with_gpio()`]
+    Gpio,
+    /// See [`Searcher::with_i2c()`]
+    I2c,
+    /// See [`Searcher::with_spi()`]
+    Spi,
+    /// See [`Searcher::with_can()`]
+    Can,
+    /// See [`Searcher::with_tpm()`]
+    Tpm,
+    /// See [`Searcher::with_watchdog()`]
+    Watchdog,
+    /// See [`Searcher::with_smartcard()`]
+    Smartcard,
+    /// See [`Searcher::with_nfc()`]
+    Nfc,
+    /// See [`Searcher::with_rtc()`]
+    Rtc,
+    /// See [`Searcher::with_framebuffer()`]
+    Framebuffer,
+    /// See [`Searcher::with_media()`]
+    Media,
+    /// See [`Searcher::with_subdev()`]
+    Subdev,
+    /// See [`Searcher::with_radio()`]
+    Radio,
+    /// See [`Searcher::with_lirc()`]
+    Lirc,
+    /// See [`Searcher::with_sound_card()`]
+    SoundCard,
+    /// See [`Searcher::with_hwdep()`]
+    Hwdep,
+    /// See [`Searcher::with_compress()`]
+    Compress,
+    /// See [`Searcher::with_uinput()`]
+    Uinput,
+    /// See [`Searcher::with_fingerprint()`]
+    Fingerprint,
+    /// See [`Searcher::with_power_supply()`]
+    PowerSupply,
+    /// See [`Searcher::with_hwmon()`]
+    Hwmon,
+    /// See [`Searcher::with_tape()`]
+    Tape,
+    /// See [`Searcher::with_nvme()`]
+    Nvme,
+    /// See [`Searcher::with_scsi_generic()`]
+    ScsiGeneric,
+    /// See [`Searcher::with_loop()`]
+    Loop,
+    /// See [`Searcher::with_hwrng()`]
+    Hwrng,
+    /// See [`Searcher::with_accelerator()`]
+    Accelerator,
+    /// See [`Searcher::with_infiniband()`]
+    Infiniband,
+    /// See [`Searcher::with_vfio()`]
+    Vfio,
+    /// See [`Searcher::with_tun()`]
+    Tun,
+    /// See [`Searcher::with_power_device()`]
+    PowerDevice,
+    /// See [`Searcher::with_braille()`]
+    Braille,
+}
+
+impl Kind {
+    /// Every kind of device, in the order [`Searcher::with_all()`] polls
+    /// them.
+    pub const ALL: &'static [Self] = &[
+        Self::Input,
+        Self::Audio,
+        Self::Midi,
+        Self::Camera,
+        Self::Serial,
+        Self::Usb,
+        Self::Hid,
+        Self::Bluetooth,
+        Self::Joystick,
+        Self::Storage,
+        Self::SdCard,
+        Self::Network,
+        Self::Gpu,
+        Self::Display,
+        Self::Printer,
+        Self::Scanner,
+        Self::Tuner,
+        Self::Cec,
+        Self::Sensor,
+        Self::Gpio,
+        Self::I2c,
+        Self::Spi,
+        Self::Can,
+        Self::Tpm,
+        Self::Watchdog,
+        Self::Smartcard,
+        Self::Nfc,
+        Self::Rtc,
+        Self::Framebuffer,
+        Self::Media,
+        Self::Subdev,
+        Self::Radio,
+        Self::Lirc,
+        Self::SoundCard,
+        Self::Hwdep,
+        Self::Compress,
+        Self::Uinput,
+        Self::Fingerprint,
+        Self::PowerSupply,
+        Self::Hwmon,
+        Self::Tape,
+        Self::Nvme,
+        Self::ScsiGeneric,
+        Self::Loop,
+        Self::Hwrng,
+        Self::Accelerator,
+        Self::Infiniband,
+        Self::Vfio,
+        Self::Tun,
+        Self::PowerDevice,
+        Self::Braille,
+    ];
+
+    /// Get the stable name of the kind (for example, `"sound-card"`).
+    pub const fn name(self) -> &'static str {
+        use Kind::*;
+        match self {
+            Input => "input",
+            Audio => "audio",
+            Midi => "midi",
+            Camera => "camera",
+            Serial => "serial",
+            Usb => "usb",
+            Hid => "hid",
+            Bluetooth => "bluetooth",
+            Joystick => "joystick",
+            Storage => "storage",
+            SdCard => "sd-card",
+            Network => "network",
+            Gpu => "gpu",
+            Display => "display",
+            Printer => "printer",
+            Scanner => "scanner",
+            Tuner => "tuner",
+            Cec => "cec",
+            Sensor => "sensor",
+            Gpio => "gpio",
+            I2c => "i2c",
+            Spi => "spi",
+            Can => "can",
+            Tpm => "tpm",
+            Watchdog => "watchdog",
+            Smartcard => "smartcard",
+            Nfc => "nfc",
+            Rtc => "rtc",
+            Framebuffer => "framebuffer",
+            Media => "media",
+            Subdev => "subdev",
+            Radio => "radio",
+            Lirc => "lirc",
+            SoundCard => "sound-card",
+            Hwdep => "hwdep",
+            Compress => "compress",
+            Uinput => "uinput",
+            Fingerprint => "fingerprint",
+            PowerSupply => "power-supply",
+            Hwmon => "hwmon",
+            Tape => "tape",
+            Nvme => "nvme",
+            ScsiGeneric => "scsi-generic",
+            Loop => "loop",
+            Hwrng => "hwrng",
+            Accelerator => "accelerator",
+            Infiniband => "infiniband",
+            Vfio => "vfio",
+            Tun => "tun",
+            PowerDevice => "power-device",
+            Braille => "braille",
+        }
+    }
+
+    /// Look up a kind by its [name](Kind::name), or `None` for unknown
+    /// names.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|kind| kind.name() == name)
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
 }
 
+/// What a searcher looks for
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Target {
+    /// Devices of a kind, in the platform's directories for it
+    Kind(Kind),
+    /// Device nodes in a directory chosen by the user
+    Custom(Dir),
+}
+
+impl Target {
+    /// Get a stable name for the target
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Kind(kind) => kind.name(),
+            Self::Custom(_) => "custom",
+        }
+    }
+
+    /// Get the kind of device, unless it's a custom directory
+    fn kind(&self) -> Option<Kind> {
+        match self {
+            Self::Kind(kind) => Some(*kind),
+            Self::Custom(_) => None,
+        }
+    }
+}
+
+/// Directory where device nodes are found
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Dir {
+    path: Cow<'static, str>,
+    /// Filename prefixes of device nodes
+    prefixes: Vec<Cow<'static, str>>,
+    /// Whether or not to look in subdirectories
+    recursive: bool,
+    /// Whether or not to only look for block devices
+    block: bool,
+    /// Whether or not to skip partitions
+    whole: bool,
+    /// USB interface class a device must have
+    class: Option<u8>,
+    /// Whether or not to skip device nodes that can't be written to
+    writable: bool,
+    /// USB vendor IDs a device must have one of, if any
+    vendors: &'static [u16],
+    /// Whether or not prefixes must be followed by a number
+    numbered: bool,
+    /// Subdirectories to look in (when recursive), if not all of them
+    subdirs: &'static [&'static str],
+    /// HID usage pages a device must use one of, if any
+    usage_pages: &'static [u16],
+}
+
+// Not every platform finds devices in (sub)directories
+#[allow(dead_code)]
+impl Dir {
+    fn new(path: &'static str, prefixes: &'static [&'static str]) -> Self {
+        let prefixes = prefixes.iter().map(|&prefix| prefix.into()).collect();
+
+        Self::custom(path.into(), prefixes)
+    }
+
+    /// Create a directory chosen at runtime
+    fn custom(
+        path: Cow<'static, str>,
+        prefixes: Vec<Cow<'static, str>>,
+    ) -> Self {
+        let recursive = false;
+        let block = false;
+        let whole = false;
+        let class = None;
+        let writable = false;
+        let vendors = &[];
+        let numbered = false;
+        let subdirs = &[];
+        let usage_pages = &[];
+
+        Self {
+            path,
+            prefixes,
+            recursive,
+            block,
+            whole,
+            class,
+            writable,
+            vendors,
+            numbered,
+            subdirs,
+            usage_pages,
+        }
+    }
+
+    /// Look in subdirectories too
+    fn recursive(mut self) -> Self {
+        self.recursive = true;
+        self
+    }
+
+    /// Skip device nodes that aren't block devices
+    fn block(mut self) -> Self {
+        self.block = true;
+        self
+    }
+
+    /// Skip partitions, only finding whole disks
+    fn whole(mut self) -> Self {
+        self.whole = true;
+        self
+    }
+
+    /// Skip USB devices without an interface of a class
+    fn class(mut self, class: u8) -> Self {
+        self.class = Some(class);
+        self
+    }
+
+    /// Skip device nodes that can't be written to
+    fn writable(mut self) -> Self {
+        self.writable = true;
+        self
+    }
+
+    /// Skip USB devices not made by one of these vendors
+    fn vendors(mut self, vendors: &'static [u16]) -> Self {
+        self.vendors = vendors;
+        self
+    }
+
+    /// Skip device nodes without a number right after the prefix
+    fn numbered(mut self) -> Self {
+        self.numbered = true;
+        self
+    }
+
+    /// Look in some subdirectories too
+    fn within(mut self, subdirs: &'static [&'static str]) -> Self {
+        self.recursive = true;
+        self.subdirs = subdirs;
+        self
+    }
+
+    /// Skip HID devices that don't use one of these usage pages
+    fn usage_pages(mut self, usage_pages: &'static [u16]) -> Self {
+        self.usage_pages = usage_pages;
+        self
+    }
+
+    /// Check if a subdirectory path is one to look in
+    fn searches(&self, path: &Path) -> bool {
+        let Ok(name) = path.strip_prefix(&*self.path) else {
+            return false;
+        };
+        let Some(name) = name.iter().next() else {
+            return false;
+        };
+
+        self.recursive
+            && (self.subdirs.is_empty()
+                || self.subdirs.iter().any(|subdir| name == *subdir))
+    }
+
+    /// Check if a device node path is one being looked for
+    fn matches(&self, path: &Path) -> bool {
+        let Ok(name) = path.strip_prefix(&*self.path) else {
+            return false;
+        };
+
+        if let Some(subdir) = name.parent().and_then(|dir| dir.iter().next()) {
+            if !self.searches(&Path::new(&*self.path).join(subdir)) {
+                return false;
+            }
+        }
+
+        // Prefixes are ASCII, so names that aren't UTF-8 still match them
+        let Some(name) = name.file_name() else {
+            return false;
+        };
+        let name = name.to_string_lossy();
+
+        let numbered = |rest: &str| {
+            !self.numbered || rest.starts_with(|c: char| c.is_ascii_digit())
+        };
+
+        if !self
+            .prefixes
+            .iter()
+            .any(|prefix| name.strip_prefix(&**prefix).map_or(false, numbered))
+        {
+            return false;
+        }
+
+        if self.block && !is_block(path) {
+            return false;
+        }
+
+        if self.whole && Platform::partition(path) {
+            return false;
+        }
+
+        if self.writable && !is_writable(path) {
+            return false;
+        }
+
+        if !self.usage_pages.is_empty()
+            && !Platform::hid_usage(path, self.usage_pages)
+        {
+            return false;
+        }
+
+        if !self.vendors.is_empty() {
+            let vendor = Platform::usb_vendor(path);
+
+            if !vendor.map_or(false, |vendor| self.vendors.contains(&vendor)) {
+                return false;
+            }
+        }
+
+        self.class
+            .map_or(true, |class| Platform::usb_class(path, class))
+    }
+}
+
+/// Check if a path is a block device
+#[cfg(unix)]
+fn is_block(path: &Path) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+
+    std::fs::metadata(path)
+        .map_or(false, |metadata| metadata.file_type().is_block_device())
+}
+
+/// Check if a path is a block device
+#[cfg(not(unix))]
+fn is_block(_path: &Path) -> bool {
+    true
+}
+
+/// Check if a path can be written to by this process
+#[cfg(unix)]
+fn is_writable(path: &Path) -> bool {
+    use std::{
+        ffi::CString,
+        os::{
+            raw::{c_char, c_int},
+            unix::ffi::OsStrExt,
+        },
+    };
+
+    const W_OK: c_int = 2;
+
+    extern "C" {
+        fn access(path: *const c_char, mode: c_int) -> c_int;
+    }
+
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+
+    unsafe { access(path.as_ptr(), W_OK) == 0 }
+}
+
+/// Check if a path can be written to by this process
+#[cfg(not(unix))]
+fn is_writable(_path: &Path) -> bool {
+    true
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 enum Events {
     Read(),
     Write(),
     All(),
 }
 
+impl Events {
+    /// Get the access mode granted by opening with these events
+    const fn access(self) -> Access {
+        match self {
+            Self::Read() => Access::Read,
+            Self::Write() => Access::Write,
+            Self::All() => Access::ReadWrite,
+        }
+    }
+}
+
+/// Access mode obtained when connecting to a [`Found`] device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Access {
+    /// Input and output
+    ReadWrite,
+    /// Input only
+    Read,
+    /// Output only
+    Write,
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ReadWrite => "read+write",
+            Self::Read => "read-only",
+            Self::Write => "write-only",
+        })
+    }
+}
+
+/// Type of node found by an [NVMe](Searcher::with_nvme) searcher.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NvmeNode {
+    /// Controller, for admin commands (like `/dev/nvme0`)
+    Controller,
+    /// Namespace, holding the storage (like `/dev/nvme0n1`)
+    Namespace,
+}
+
+/// Mechanism a [`Searcher`] uses to find devices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Backend {
+    /// Watching device directories with inotify (Linux)
+    Inotify,
+    /// Listening for kernel uevents over netlink (Linux)
+    Uevent,
+    /// Listening for network interface changes over rtnetlink (Linux)
+    Rtnetlink,
+    /// Periodically rescanning device directories
+    Scan,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Inotify => "inotify",
+            Self::Uevent => "uevents",
+            Self::Rtnetlink => "rtnetlink",
+            Self::Scan => "scanning",
+        })
+    }
+}
+
+/// Kernel driver feature a [`Searcher`] can [require](Searcher::require) of
+/// devices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Feature {
+    /// Input devices that support revoking access (`EVIOCREVOKE`), for
+    /// handing them over between sessions
+    Revoke,
+    /// Input devices that support force feedback
+    ForceFeedback,
+    /// Cameras that support single-planar video capture
+    Capture,
+    /// Cameras that support multi-planar video capture (V4L2 `MPLANE`)
+    Multiplanar,
+}
+
 /// Platform implementation
 struct Platform;
 
 /// Interface should be implemented for each `Platform`
 trait Interface {
+    type Closed: Notify<Event = ()> + Send + Unpin;
     type Searcher: Notify<Event = Found> + Send + Unpin;
 
-    /// Create a searcher for a specific type of device
-    fn searcher(kind: Kind) -> Option<Self::Searcher>;
+    /// Create a searcher for a specific type of device, recording backends
+    /// that couldn't be used
+    fn searcher(
+        builder: &SearcherBuilder,
+        attempts: &mut Vec<Attempt>,
+    ) -> Option<Self::Searcher>;
+
+    /// Get the backend a searcher is using
+    fn backend(searcher: &Self::Searcher) -> Backend;
+
+    /// Detect sandbox restrictions, given the backends that couldn't be used
+    fn restrictions(attempts: &[Attempt]) -> Vec<Restriction>;
 
     /// Try to watch a found device for both read+write events
-    fn open(found: Found, events: Events) -> Result<Device, Found>;
+    fn open(
+        found: Found,
+        options: &ConnectOptions,
+    ) -> Result<Device, (Found, io::Error)>;
+
+    /// Try to open a found device, without watching it
+    #[cfg(any(unix, target_os = "wasi"))]
+    fn open_fd(
+        found: Found,
+        options: &ConnectOptions,
+    ) -> Result<OwnedFd, (Found, io::Error)>;
+
+    /// Get an identifier for the physical device a device node belongs to
+    fn parent(path: &Path) -> Option<String>;
+
+    /// Get a stable identifier for the physical device a device node belongs
+    /// to, that stays the same when it's reconnected
+    fn id(path: &Path) -> Option<String>;
+
+    /// Get a symlink to a device node named after its physical device, that
+    /// stays the same when it's reconnected
+    fn stable_path(path: &Path) -> Option<PathBuf>;
+
+    /// Check if the device manager has finished setting up a device node, so
+    /// its stable path is there if it's going to have one
+    fn settled(path: &Path) -> bool;
+
+    /// Get an identifier for the USB port a device node's physical device is
+    /// plugged into
+    fn port(path: &Path) -> Option<String>;
+
+    /// Get a human-readable name for a device node
+    fn name(path: &Path) -> Option<String>;
+
+    /// Check if a storage device node is a partition (rather than a whole
+    /// disk)
+    fn partition(path: &Path) -> bool;
+
+    /// Get the index of a device node among the ones of its kind that belong
+    /// to the same physical device (0 for the main one)
+    fn index(path: &Path) -> Option<u32>;
+
+    /// Check if a USB device node has an interface of a class
+    fn usb_class(path: &Path, class: u8) -> bool;
+
+    /// Get the vendor ID of a USB device node's physical device
+    fn usb_vendor(path: &Path) -> Option<u16>;
+
+    /// Get the vendor ID, product ID and serial number of the USB device a
+    /// device node belongs to
+    fn usb_info(path: &Path) -> Option<UsbInfo>;
+
+    /// Get the bus, driver, device number and subsystem of a device node
+    fn device_info(path: &Path) -> DeviceInfo;
+
+    /// Check if a HID device node uses one of these usage pages
+    fn hid_usage(path: &Path, pages: &[u16]) -> bool;
+
+    /// Check if a device node's driver supports a feature
+    fn supports(path: &Path, feature: Feature) -> bool;
+
+    /// Get the capture metadata of a camera's device node
+    fn video_info(path: &Path) -> io::Result<VideoInfo>;
+
+    /// Get the file descriptor that's readable when a searcher has more to
+    /// find, if it has one
+    #[cfg(any(unix, target_os = "wasi"))]
+    fn fd(searcher: &Self::Searcher) -> Option<BorrowedFd<'_>>;
+
+    /// Take the paths of devices removed, that were noticed while searching
+    fn take_removed(searcher: &mut Self::Searcher) -> VecDeque<PathBuf>;
+
+    /// Watch for a device node being closed by any process
+    fn watch_close(path: &Path) -> Option<Self::Closed>;
+
+    /// Watch for changes to a device node, that may be it being removed
+    fn watch_removal(path: &Path) -> Option<Self::Closed>;
+
+    /// List the devices of a kind that are there now
+    fn enumerate(kind: Kind) -> Vec<PathBuf>;
+
+    /// Save a searcher's state, leaving open the file descriptor it watches
+    fn save(searcher: Self::Searcher, snapshot: &mut Snapshot);
+
+    /// Restore a searcher from its saved state
+    ///
+    /// # Safety
+    ///
+    /// The snapshot's file descriptor must be open, and not owned by anything
+    /// else in this process.
+    unsafe fn restore(snapshot: &Snapshot) -> Option<Self::Searcher>;
+}
+
+/// List the devices of a kind that are connected now, without watching for
+/// more.
+///
+/// This is quicker than running a [`Searcher`], for tools that list devices
+/// and exit.
+///
+/// ```rust
+/// use lookit::Kind;
+///
+/// for found in lookit::enumerate(Kind::Serial) {
+///     println!("{}", found.path().display());
+/// }
+/// ```
+pub fn enumerate(kind: Kind) -> Vec<Found> {
+    let mut paths = Platform::enumerate(kind);
+
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let mut found = Found::new(path);
+
+            found.kind = Some(kind);
+            found
+        })
+        .collect()
 }
 
 /// Lookit [`Notify`].  Lets you know when a device is [`Found`].
-pub struct Searcher(Cell<Option<<Platform as Interface>::Searcher>>);
+pub struct Searcher {
+    target: Target,
+    notifier: Option<<Platform as Interface>::Searcher>,
+    status: Status,
+    limit: Option<Limit>,
+    parked: VecDeque<Found>,
+    debounce: Option<Debounce>,
+    aliases: Option<Aliases>,
+    /// Devices restored from a snapshot, not yet filtered
+    restored: VecDeque<Found>,
+    filters: Filters,
+    /// Paths of devices removed, when tracked for [`Changes`]
+    removed: Option<VecDeque<PathBuf>>,
+    /// Puts stable paths on devices found, when preferred
+    stable: Option<Stable>,
+}
 
 impl fmt::Debug for Searcher {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -102,64 +1115,1349 @@ impl fmt::Debug for Searcher {
 }
 
 impl Searcher {
+    /// Create new notifier checking for devices of a kind, for choosing the
+    /// kind at runtime (for example, from a configuration file).
+    ///
+    /// ```rust, no_run
+    /// use lookit::{Kind, Searcher};
+    /// use pasts::prelude::*;
+    ///
+    /// #[async_main::async_main]
+    /// async fn main(_spawner: impl async_main::Spawn) {
+    ///     let kind = Kind::from_name("midi").expect("unknown kind");
+    ///     let found = Searcher::new(kind).next().await;
+    ///
+    ///     dbg!(found);
+    /// }
+    /// ```
+    pub fn new(kind: Kind) -> Self {
+        SearcherBuilder::new(kind).build()
+    }
+
+    /// Create new notifier checking for devices of a kind, or get the
+    /// [`Status`] explaining why it can't search (for example, when devices of
+    /// this kind can't be found on this platform).
+    ///
+    /// [`Searcher::new()`] returns a searcher that never finds anything in
+    /// that case instead.
+    ///
+    /// ```rust
+    /// use lookit::{Kind, Searcher};
+    ///
+    /// match Searcher::try_new(Kind::Serial) {
+    ///     Ok(searcher) => println!("{}", searcher.status()),
+    ///     Err(status) => eprintln!("Can't look for serial ports: {status}"),
+    /// }
+    /// ```
+    pub fn try_new(kind: Kind) -> Result<Self, Status> {
+        SearcherBuilder::new(kind).try_build()
+    }
+
+    fn build(builder: &SearcherBuilder) -> Self {
+        let target = builder.target.clone();
+        let mut attempts = Vec::new();
+        let notifier = Platform::searcher(builder, &mut attempts);
+        let backend = notifier.as_ref().map(Platform::backend);
+        let restrictions = Platform::restrictions(&attempts);
+        let status = Status::new(backend, attempts, restrictions);
+        let limit = None;
+        let parked = VecDeque::new();
+        let debounce = None;
+        let aliases = None;
+        let restored = VecDeque::new();
+        let filters = builder.filters.clone();
+        let removed = None;
+        let stable = None;
+
+        Self {
+            target,
+            notifier,
+            status,
+            limit,
+            parked,
+            debounce,
+            aliases,
+            restored,
+            filters,
+            removed,
+            stable,
+        }
+    }
+
+    /// Restore a searcher saved with [`Searcher::snapshot()`] before an
+    /// `exec()`.
+    ///
+    /// Devices added while the process was restarting are found as usual,
+    /// without finding the ones that were already there again.  If the saved
+    /// state can't be restored, searching starts over from scratch.
+    ///
+    /// # Safety
+    ///
+    /// The snapshot's [file descriptor](Snapshot::fd()), if any, must be open
+    /// and not owned by anything else in this process, like one left open
+    /// across `exec()` by [`Searcher::snapshot()`].  The searcher takes
+    /// ownership of it, closing it when dropped.  Snapshots parsed from text
+    /// that isn't trusted must not be restored.
+    pub unsafe fn restore(snapshot: Snapshot) -> Self {
+        let Some(notifier) = Platform::restore(&snapshot) else {
+            return SearcherBuilder::with_target(snapshot.target.clone())
+                .build();
+        };
+        let target = snapshot.target.clone();
+        let backend = Some(Platform::backend(&notifier));
+        let restrictions = Platform::restrictions(&[]);
+        let status = Status::new(backend, Vec::new(), restrictions);
+        let notifier = Some(notifier);
+        let limit = None;
+        let parked = VecDeque::new();
+        let debounce = None;
+        let aliases = None;
+        let restored = snapshot.pending.into_iter().map(Found::new).collect();
+        let filters = Filters::default();
+        let removed = None;
+        let stable = None;
+
+        Self {
+            target,
+            notifier,
+            status,
+            limit,
+            parked,
+            debounce,
+            aliases,
+            restored,
+            filters,
+            removed,
+            stable,
+        }
+    }
+
+    /// Save the searcher's state, so it can be [restored](Searcher::restore)
+    /// after an `exec()`.
+    ///
+    /// Any [`limit()`](Searcher::limit), [`debounce()`](Searcher::debounce),
+    /// [`aliases()`](Searcher::aliases),
+    /// [`stable_paths()`](Searcher::stable_paths),
+    /// [required features](Searcher::require), [patterns](Searcher::matching),
+    /// [USB IDs](Searcher::vendor) or [filters](Searcher::filter) aren't
+    /// saved, and must be set again.
+    pub fn snapshot(self) -> Snapshot {
+        let backend = self.backend();
+        let mut snapshot = Snapshot::new(self.target, backend);
+
+        snapshot.pending = self
+            .restored
+            .into_iter()
+            .chain(self.debounce.into_iter().flat_map(Debounce::into_pending))
+            .chain(self.stable.into_iter().flat_map(Stable::into_pending))
+            .chain(self.parked)
+            .map(|found| found.path)
+            .collect();
+
+        if let Some(notifier) = self.notifier {
+            Platform::save(notifier, &mut snapshot);
+        }
+
+        snapshot
+    }
+
+    /// Label found devices from an alias table, see [`Found::alias()`].
+    pub fn aliases(mut self, aliases: Aliases) -> Self {
+        self.aliases = Some(aliases);
+        self
+    }
+
+    /// Report devices by their [stable path](Found::stable_path) when they
+    /// have one, instead of by their device node.
+    ///
+    /// Device node names depend on the order devices are plugged in, so this
+    /// lets a device chosen by path be recognized again later.  Removals are
+    /// reported by the same path for [`Changes`].
+    ///
+    /// The device manager creates stable paths shortly after the device node,
+    /// so new devices are held back until it's done with them (for up to a
+    /// second).  Everything else about the device, like its
+    /// [port](Found::port), still comes from its device node.
+    pub fn stable_paths(mut self) -> Self {
+        self.stable = Some(Stable::default());
+        self
+    }
+
+    /// Only find devices whose kernel driver supports a feature, so devices
+    /// that can't be used are never seen.
+    ///
+    /// Can be called more than once to require multiple features.  Devices
+    /// are skipped when support can't be checked, which is always the case on
+    /// platforms other than Linux.
+    pub fn require(mut self, feature: Feature) -> Self {
+        self.filters.features.push(feature);
+        self
+    }
+
+    /// Only find devices with a name matching a glob pattern.
+    ///
+    /// Patterns support `*` (any run of characters), `?` (any one character)
+    /// and character classes (like `[0-9]` or `[!0-9]`), with alternatives
+    /// separated by `|`.  Patterns containing a `/` are matched against the
+    /// whole path, rather than just the name.
+    ///
+    /// Can be called more than once, to only find devices matching every
+    /// pattern.  Patterns can be built at runtime, like from a configuration
+    /// file.
+    ///
+    /// ```rust
+    /// use lookit::Searcher;
+    ///
+    /// // USB serial adapters, but not the built-in serial ports
+    /// let searcher = Searcher::with_serial().matching("ttyUSB*|ttyACM*");
+    /// # drop(searcher);
+    ///
+    /// // Only the port passed on the command line, if any
+    /// let port = std::env::args().nth(1).unwrap_or_else(|| "*".to_string());
+    /// let searcher = Searcher::with_serial().matching(port);
+    /// # drop(searcher);
+    /// ```
+    pub fn matching(mut self, pattern: impl Into<Cow<'static, str>>) -> Self {
+        self.filters.patterns.push(pattern.into());
+        self
+    }
+
+    /// Only find devices with this USB vendor ID.
+    ///
+    /// ```rust
+    /// use lookit::Searcher;
+    ///
+    /// // Logitech C920 webcams
+    /// let searcher = Searcher::with_camera().vendor(0x046d).product(0x082d);
+    /// # drop(searcher);
+    /// ```
+    pub fn vendor(mut self, vendor: u16) -> Self {
+        self.filters.vendor = Some(vendor);
+        self
+    }
+
+    /// Only find devices with this USB product ID, usually along with a
+    /// [`vendor()`](Searcher::vendor) since product IDs are chosen by each
+    /// vendor.
+    pub fn product(mut self, product: u16) -> Self {
+        self.filters.product = Some(product);
+        self
+    }
+
+    /// Only find devices that `predicate` returns `true` for.
+    ///
+    /// Devices are filtered before they're delivered or count towards the
+    /// [`limit()`](Searcher::limit), so skipped devices are never parked.
+    /// Can be called more than once, to only find devices passing every
+    /// predicate.
+    ///
+    /// ```rust
+    /// use lookit::Searcher;
+    ///
+    /// let searcher = Searcher::with_camera().filter(|found| {
+    ///     found.name().map_or(false, |name| name.contains("C920"))
+    /// });
+    /// # drop(searcher);
+    /// ```
+    pub fn filter(
+        mut self,
+        predicate: impl Fn(&Found) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.filters.predicates.push(Arc::new(predicate));
+        self
+    }
+
+    /// Create new notifier checking for devices of every kind, see
+    /// [`Found::kind()`] to tell them apart.
+    ///
+    /// This runs a searcher for each kind, which each use their own backend.
+    pub fn with_all() -> Merged {
+        Merged::new(Kind::ALL.iter().copied().map(Self::new).collect())
+    }
+
     /// Create new future checking for input devices.
     pub fn with_input() -> Self {
-        Self(Platform::searcher(Kind::Input()).into())
+        Self::new(Kind::Input)
+    }
+
+    /// Create new future checking for legacy joystick devices.
+    ///
+    /// These are the `/dev/input/js*` nodes of the old Linux joystick API,
+    /// which gamepads also show up as alongside their [input](Self::with_input)
+    /// event nodes.
+    pub fn with_joystick() -> Self {
+        Self::new(Kind::Joystick)
     }
 
     /// Create new future checking for audio devices (speakers, microphones).
     pub fn with_audio() -> Self {
-        Self(Platform::searcher(Kind::Audio()).into())
+        Self::new(Kind::Audio)
     }
 
     /// Create new future checking for MIDI devices.
+    ///
+    /// On Linux, this includes the ALSA sequencer once it's available, see
+    /// [`Found::is_sequencer()`].
     pub fn with_midi() -> Self {
-        Self(Platform::searcher(Kind::Midi()).into())
+        Self::new(Kind::Midi)
     }
 
     /// Create new future checking for camera devices.
     pub fn with_camera() -> Self {
-        Self(Platform::searcher(Kind::Camera()).into())
+        Self::new(Kind::Camera)
+    }
+
+    /// Get the backend in use, or `None` if devices of this kind can't be
+    /// searched for (or the searcher was [closed](Searcher::close)).
+    ///
+    /// On Linux, backends are tried in order of preference (inotify, then
+    /// uevents, then scanning) until one is available.
+    pub fn backend(&self) -> Option<Backend> {
+        self.status.backend()
+    }
+
+    /// Check whether or not the searcher will ever find any more devices, which
+    /// it won't once it's [closed](Searcher::close) or when no backend could
+    /// be used (see [`Searcher::status()`]).
+    pub fn is_active(&self) -> bool {
+        self.status.is_active()
+    }
+
+    /// Take the status, once the searcher isn't needed
+    fn into_status(self) -> Status {
+        self.status
+    }
+
+    /// Stop searching, and release what's used to watch for devices (like the
+    /// inotify file descriptor, along with its watches) without dropping the
+    /// searcher.
+    ///
+    /// Devices found but not yet delivered are discarded, and nothing is found
+    /// afterwards.  Removals already noticed are still reported by
+    /// [`Changes`].  Dropping a searcher releases the same resources.
+    pub fn close(&mut self) {
+        self.notifier = None;
+        self.restored.clear();
+        self.parked.clear();
+
+        if let Some(ref mut debounce) = self.debounce {
+            debounce.clear();
+        }
+
+        if let Some(ref mut stable) = self.stable {
+            stable.clear();
+        }
+
+        self.status.close();
+    }
+
+    /// Get diagnostics describing which backends were attempted, and why
+    /// they couldn't be used.
+    ///
+    /// ```rust
+    /// let searcher = lookit::Searcher::with_midi();
+    ///
+    /// if !searcher.status().is_active() {
+    ///     eprintln!("Can't search for MIDI devices: {}", searcher.status());
+    /// }
+    /// ```
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    /// Get the guarantees of how devices are delivered by the backend in use
+    /// (or the [minimum](DeliveryGuarantees::MINIMUM) when there isn't one).
+    pub fn guarantees(&self) -> DeliveryGuarantees {
+        self.backend()
+            .map_or(DeliveryGuarantees::MINIMUM, Backend::guarantees)
+    }
+
+    /// Limit how many [`Found`]s may be outstanding (neither connected nor
+    /// dropped) at once.
+    ///
+    /// Devices found while at the limit are parked, and delivered in the order
+    /// they were found as earlier ones get connected or dropped.
+    pub fn limit(mut self, max: usize) -> Self {
+        self.limit = Some(Limit::new(max));
+        self
+    }
+
+    /// Coalesce devices found for the same path within `window` of each other
+    /// into one [`Found`].
+    ///
+    /// Plugging in a device can cause a burst of events for one node (for
+    /// example, when udev changes its permissions right after it's created).
+    /// Each device is delivered once no more events for its path have come in
+    /// for `window`, and devices removed before then aren't delivered at all.
+    /// The [timestamp](Found::timestamp) is kept from the first event.
+    pub fn debounce(mut self, window: Duration) -> Self {
+        self.debounce = Some(Debounce::new(window));
+        self
+    }
+
+    /// Create new future checking for serial ports (USB serial adapters,
+    /// modems, and Bluetooth RFCOMM).
+    pub fn with_serial() -> Self {
+        Self::new(Kind::Serial)
+    }
+
+    /// Create new future checking for raw USB devices.
+    ///
+    /// Found devices are bus/device paths (like `/dev/bus/usb/001/005`), which
+    /// libusb-style USB libraries can open to talk to arbitrary hardware.
+    pub fn with_usb() -> Self {
+        Self::new(Kind::Usb)
+    }
+
+    /// Create new future checking for raw HID devices (macro pads, fight
+    /// sticks, and other custom peripherals).
+    pub fn with_hid() -> Self {
+        Self::new(Kind::Hid)
+    }
+
+    /// Create new future checking for Bluetooth adapters (like USB dongles).
+    ///
+    /// Bluetooth adapters don't have device nodes, so on Linux found devices
+    /// are sysfs directories (like `/sys/class/bluetooth/hci0`), named after
+    /// the HCI device to open with a Bluetooth socket.
+    pub fn with_bluetooth() -> Self {
+        Self::new(Kind::Bluetooth)
+    }
+
+    /// Create new future checking for block storage devices (USB drives, SATA
+    /// and NVMe disks, and SD cards).
+    ///
+    /// Both whole disks and their partitions are found, use
+    /// [`Found::is_partition()`] to tell them apart.
+    pub fn with_storage() -> Self {
+        Self::new(Kind::Storage)
+    }
+
+    /// Create new future checking for SD/MMC cards being inserted.
+    ///
+    /// Only whole cards are found (not their partitions); card readers and
+    /// MMC controllers don't have device nodes, so they are never found
+    /// themselves.
+    pub fn with_sd_card() -> Self {
+        Self::new(Kind::SdCard)
+    }
+
+    /// Create new future checking for network interfaces (like USB Ethernet
+    /// and Wi-Fi adapters).
+    ///
+    /// Network interfaces don't have device nodes, so on Linux found devices
+    /// are sysfs directories named after the interface (like
+    /// `/sys/class/net/wlan0`).
+    pub fn with_network() -> Self {
+        Self::new(Kind::Network)
+    }
+
+    /// Create new future checking for GPUs (DRM primary and render nodes),
+    /// like eGPUs or GPUs whose drivers load late.
+    pub fn with_gpu() -> Self {
+        Self::new(Kind::Gpu)
+    }
+
+    /// Create new future checking for displays (monitors plugged into a GPU's
+    /// HDMI, DisplayPort or other connectors).
+    pub fn with_display() -> Self {
+        Self::new(Kind::Display)
+    }
+
+    /// Create new future checking for printers (USB and parallel port), without
+    /// needing a print server like CUPS.
+    pub fn with_printer() -> Self {
+        Self::new(Kind::Printer)
+    }
+
+    /// Create new future checking for USB scanners (USB devices with a still
+    /// image interface), for document scanning.
+    pub fn with_scanner() -> Self {
+        Self::new(Kind::Scanner)
+    }
+
+    /// Create new future checking for DVB TV tuners (frontend and demux nodes),
+    /// like USB DVB sticks.
+    pub fn with_tuner() -> Self {
+        Self::new(Kind::Tuner)
+    }
+
+    /// Create new future checking for HDMI CEC adapters, for controlling TVs
+    /// and other devices connected over HDMI.
+    pub fn with_cec() -> Self {
+        Self::new(Kind::Cec)
+    }
+
+    /// Create new future checking for IIO sensors, like accelerometers, light
+    /// sensors, and USB sensor dongles.
+    pub fn with_sensors() -> Self {
+        Self::new(Kind::Sensor)
+    }
+
+    /// Create new future checking for GPIO chips, like USB GPIO expanders.
+    pub fn with_gpio() -> Self {
+        Self::new(Kind::Gpio)
+    }
+
+    /// Create new future checking for I2C adapters, like USB-to-I2C bridges.
+    pub fn with_i2c() -> Self {
+        Self::new(Kind::I2c)
+    }
+
+    /// Create new future checking for SPI devices exposed to userspace.
+    pub fn with_spi() -> Self {
+        Self::new(Kind::Spi)
+    }
+
+    /// Create new future checking for SocketCAN interfaces (`can*` and
+    /// `vcan*`), like USB CAN adapters.
+    pub fn with_can() -> Self {
+        Self::new(Kind::Can)
+    }
+
+    /// Create new future checking for TPMs (and their resource manager nodes),
+    /// to wait for them to be available.
+    pub fn with_tpm() -> Self {
+        Self::new(Kind::Tpm)
+    }
+
+    /// Create new future checking for hardware watchdogs, to attach to them as
+    /// soon as their driver loads.
+    pub fn with_watchdog() -> Self {
+        Self::new(Kind::Watchdog)
+    }
+
+    /// Create new future checking for USB smartcard readers (USB devices with a
+    /// CCID interface).
+    pub fn with_smartcard() -> Self {
+        Self::new(Kind::Smartcard)
+    }
+
+    /// Create new future checking for NFC devices, like USB NFC dongles.
+    pub fn with_nfc() -> Self {
+        Self::new(Kind::Nfc)
+    }
+
+    /// Create new future checking for real-time clocks, like RTC modules on
+    /// hot-pluggable buses.
+    pub fn with_rtc() -> Self {
+        Self::new(Kind::Rtc)
+    }
+
+    /// Create new future checking for framebuffers, like USB DisplayLink
+    /// adapters.
+    pub fn with_framebuffer() -> Self {
+        Self::new(Kind::Framebuffer)
+    }
+
+    /// Create new future checking for media controllers, the nodes describing
+    /// the media graph of complex cameras.
+    pub fn with_media() -> Self {
+        Self::new(Kind::Media)
+    }
+
+    /// Create new future checking for V4L2 subdevices, like camera sensors
+    /// configured directly.
+    pub fn with_subdev() -> Self {
+        Self::new(Kind::Subdev)
+    }
+
+    /// Create new future checking for V4L2 radio tuners, like USB FM radios.
+    pub fn with_radio() -> Self {
+        Self::new(Kind::Radio)
+    }
+
+    /// Create new future checking for IR receivers (and transmitters), for
+    /// remote controls.
+    pub fn with_lirc() -> Self {
+        Self::new(Kind::Lirc)
+    }
+
+    /// Create new future checking for sound cards, found once per card (by its
+    /// control node) rather than once per PCM device.
+    pub fn with_sound_card() -> Self {
+        Self::new(Kind::SoundCard)
+    }
+
+    /// Create new future checking for ALSA hardware dependent devices, used by
+    /// DSP and firmware tools.
+    pub fn with_hwdep() -> Self {
+        Self::new(Kind::Hwdep)
+    }
+
+    /// Create new future checking for ALSA compressed audio offload devices.
+    pub fn with_compress() -> Self {
+        Self::new(Kind::Compress)
+    }
+
+    /// Create new future checking for uinput, found once it's available and can
+    /// be written to (for creating virtual input devices).
+    pub fn with_uinput() -> Self {
+        Self::new(Kind::Uinput)
+    }
+
+    /// Create new future checking for USB fingerprint readers, recognized by
+    /// their vendor (since they don't have a USB interface class of their own).
+    pub fn with_fingerprint() -> Self {
+        Self::new(Kind::Fingerprint)
+    }
+
+    /// Create new future checking for power supplies, like USB-PD chargers,
+    /// UPSes and hot-swapped batteries.
+    pub fn with_power_supply() -> Self {
+        Self::new(Kind::PowerSupply)
+    }
+
+    /// Create new future checking for hardware monitoring devices, like fan
+    /// controllers and external temperature probes.
+    pub fn with_hwmon() -> Self {
+        Self::new(Kind::Hwmon)
+    }
+
+    /// Create new future checking for SCSI tape drives, like USB and SAS tape
+    /// drives.
+    pub fn with_tape() -> Self {
+        Self::new(Kind::Tape)
+    }
+
+    /// Create new future checking for NVMe controllers and namespaces, told
+    /// apart with [`Found::nvme_node()`].
+    pub fn with_nvme() -> Self {
+        Self::new(Kind::Nvme)
+    }
+
+    /// Create new future checking for SCSI generic devices, for tools that
+    /// speak raw SCSI (like disc burners and enclosures).
+    pub fn with_scsi_generic() -> Self {
+        Self::new(Kind::ScsiGeneric)
+    }
+
+    /// Create new future checking for loop devices, like ones allocated by
+    /// other processes for disk images.
+    pub fn with_loop() -> Self {
+        Self::new(Kind::Loop)
+    }
+
+    /// Create new future checking for the hardware random number generator,
+    /// found once its driver loads.
+    pub fn with_hwrng() -> Self {
+        Self::new(Kind::Hwrng)
+    }
+
+    /// Create new future checking for AI accelerators (NPUs, TPUs and compute
+    /// GPUs), for ML runtimes.
+    pub fn with_accelerator() -> Self {
+        Self::new(Kind::Accelerator)
+    }
+
+    /// Create new future checking for InfiniBand and RDMA devices, like RDMA
+    /// NICs.
+    pub fn with_infiniband() -> Self {
+        Self::new(Kind::Infiniband)
+    }
+
+    /// Create new future checking for VFIO groups, found once a device is bound
+    /// to a VFIO driver (for passthrough to virtual machines).
+    pub fn with_vfio() -> Self {
+        Self::new(Kind::Vfio)
+    }
+
+    /// Create new future checking for TUN/TAP, found once it's available (when
+    /// its module is loaded).
+    pub fn with_tun() -> Self {
+        Self::new(Kind::Tun)
+    }
+
+    /// Create new future checking for HID power devices, like UPSes.
+    pub fn with_power_device() -> Self {
+        Self::new(Kind::PowerDevice)
+    }
+
+    /// Create new future checking for braille displays that use the HID braille
+    /// usage page.
+    pub fn with_braille() -> Self {
+        Self::new(Kind::Braille)
+    }
+
+    /// Create new future checking for device nodes in a directory (ending with
+    /// `/`) whose filenames start with one of `prefixes`, for device families
+    /// this crate doesn't know about.
+    ///
+    /// Searchers created this way can be [snapshotted](Searcher::snapshot),
+    /// but the text form of the snapshot can't be parsed back.  On platforms
+    /// where devices don't appear as nodes in a directory, nothing is found.
+    ///
+    /// The path and prefixes can be chosen at runtime:
+    ///
+    /// ```rust
+    /// use lookit::Searcher;
+    ///
+    /// let dir = std::env::var("DEVICE_DIR");
+    /// let dir = dir.unwrap_or_else(|_| "/dev/".to_string());
+    /// let searcher = Searcher::with_path(dir, ["ttyS", "ttyUSB"]);
+    /// # drop(searcher);
+    /// ```
+    pub fn with_path<I>(path: impl Into<Cow<'static, str>>, prefixes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        SearcherBuilder::with_path(path, prefixes).build()
+    }
+
+    /// Also find the devices found by other searchers, in one [`Notify`].
+    ///
+    /// ```rust, no_run
+    /// use lookit::{Kind, Searcher};
+    /// use pasts::prelude::*;
+    ///
+    /// #[async_main::async_main]
+    /// async fn main(_spawner: impl async_main::Spawn) {
+    ///     let mut devices = Searcher::with_camera()
+    ///         .merge([Searcher::with_audio(), Searcher::with_joystick()]);
+    ///
+    ///     loop {
+    ///         let found = devices.next().await;
+    ///
+    ///         let kind = found.kind().map_or("custom", Kind::name);
+    ///
+    ///         println!("Found {kind} device {found:?}");
+    ///     }
+    /// }
+    /// ```
+    pub fn merge(self, others: impl IntoIterator<Item = Searcher>) -> Merged {
+        Merged::new(std::iter::once(self).chain(others).collect())
+    }
+
+    /// Also let you know when devices found are removed.
+    ///
+    /// ```rust, no_run
+    /// use lookit::{Event, Searcher};
+    /// use pasts::prelude::*;
+    ///
+    /// #[async_main::async_main]
+    /// async fn main(_spawner: impl async_main::Spawn) {
+    ///     let mut changes = Searcher::with_input().changes();
+    ///
+    ///     loop {
+    ///         match changes.next().await {
+    ///             Event::Connected(found) => println!("Connected {found:?}"),
+    ///             Event::Disconnected(path) => {
+    ///                 println!("Disconnected {}", path.display())
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn changes(mut self) -> Changes {
+        self.removed = Some(VecDeque::new());
+
+        Changes::new(self)
+    }
+
+    /// Use the searcher as a `futures_core::Stream`, for executors and
+    /// combinators built on `futures` (requires the `stream` feature).
+    ///
+    /// The stream never ends.
+    #[cfg(feature = "stream")]
+    pub fn into_stream(self) -> SearchStream {
+        SearchStream::new(self)
+    }
+
+    /// Find devices without an async executor, blocking the thread until
+    /// each one is found.
+    ///
+    /// This is for command-line tools and scripts that don't otherwise use
+    /// async.
+    ///
+    /// ```rust, no_run
+    /// use lookit::Searcher;
+    ///
+    /// for found in Searcher::with_serial().blocking_iter().take(2) {
+    ///     println!("Found {}", found.path().display());
+    /// }
+    /// ```
+    pub fn blocking_iter(self) -> BlockingIter {
+        BlockingIter::new(self)
+    }
+
+    /// Run the searcher on its own thread, receiving the devices it finds
+    /// from any number of [`Receiver`]s.
+    ///
+    /// This lets devices be handled on multiple threads or tasks, without
+    /// sharing the searcher.  Not available on WASI, which doesn't have
+    /// threads.
+    ///
+    /// ```rust, no_run
+    /// use lookit::Searcher;
+    /// use pasts::prelude::*;
+    ///
+    /// #[async_main::async_main]
+    /// async fn main(_spawner: impl async_main::Spawn) {
+    ///     let mut receiver = Searcher::with_serial().into_channel();
+    ///     let worker = receiver.clone();
+    ///
+    ///     std::thread::spawn(move || {
+    ///         let mut worker = worker;
+    ///
+    ///         pasts::Executor::default().block_on(async move {
+    ///             dbg!(worker.next().await);
+    ///         });
+    ///     });
+    ///
+    ///     dbg!(receiver.next().await);
+    /// }
+    /// ```
+    #[cfg(not(target_os = "wasi"))]
+    pub fn into_channel(self) -> Receiver {
+        Receiver::new(self)
+    }
+
+    /// Get the next device found, or `None` if there isn't one yet, without
+    /// waiting.
+    ///
+    /// This is for polling from a reactor other than the executor's, see
+    /// [`Searcher::as_fd()`].  Devices held back by a
+    /// [`limit()`](Searcher::limit) are returned by a later call, once
+    /// there's room.
+    ///
+    /// For event loops without an async runtime, like mio's, register the
+    /// file descriptor and call this whenever it's readable (with the `mio`
+    /// feature, `SearcherBuilder::build_mio()` gives a searcher that can be
+    /// registered itself):
+    ///
+    /// ```rust, ignore
+    /// use std::os::fd::AsRawFd;
+    ///
+    /// use lookit::{Kind, SearcherBuilder};
+    /// use mio::{unix::SourceFd, Events, Interest, Poll, Token};
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let mut searcher =
+    ///         SearcherBuilder::new(Kind::Joystick).watched(false).build();
+    ///     let fd = searcher.as_fd().expect("no fd").as_raw_fd();
+    ///     let mut poll = Poll::new()?;
+    ///     let mut events = Events::with_capacity(16);
+    ///
+    ///     poll.registry().register(
+    ///         &mut SourceFd(&fd),
+    ///         Token(0),
+    ///         Interest::READABLE,
+    ///     )?;
+    ///
+    ///     loop {
+    ///         while let Some(found) = searcher.try_next() {
+    ///             println!("Found {found:?}");
+    ///         }
+    ///
+    ///         poll.poll(&mut events, None)?;
+    ///     }
+    /// }
+    /// ```
+    pub fn try_next(&mut self) -> Option<Found> {
+        blocking::try_poll(self)
+    }
+
+    /// Get the file descriptor that becomes readable when there's more to
+    /// find, for registering with another reactor (like tokio's `AsyncFd`).
+    ///
+    /// Once it's readable, call [`Searcher::try_next()`] until it returns
+    /// `None` before waiting again.  Returns `None` when the backend in use
+    /// doesn't have one (like [`Backend::Scan`]), in which case the searcher
+    /// must be polled as a [`Notify`].
+    ///
+    /// Devices that are found after a delay (with
+    /// [`debounce()`](Searcher::debounce), or when waiting for a device file's
+    /// permissions to change) don't make it readable, so also call
+    /// `try_next()` again after the delay.  Build the searcher with
+    /// [`watched(false)`](SearcherBuilder::watched), so smelling_salts doesn't
+    /// watch it too.  With the `tokio` feature,
+    /// `SearcherBuilder::build_tokio()` does all of this.
+    ///
+    /// ```rust, ignore
+    /// use std::os::fd::AsRawFd;
+    ///
+    /// use lookit::{Kind, SearcherBuilder};
+    /// use tokio::io::{unix::AsyncFd, Interest};
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let mut searcher =
+    ///         SearcherBuilder::new(Kind::Input).watched(false).build();
+    ///     let fd = searcher.as_fd().expect("no fd").as_raw_fd();
+    ///     let fd = AsyncFd::with_interest(fd, Interest::READABLE)?;
+    ///
+    ///     loop {
+    ///         while let Some(found) = searcher.try_next() {
+    ///             println!("Found {found:?}");
+    ///         }
+    ///
+    ///         fd.readable().await?.clear_ready();
+    ///     }
+    /// }
+    /// ```
+    #[cfg(any(unix, target_os = "wasi"))]
+    pub fn as_fd(&self) -> Option<BorrowedFd<'_>> {
+        self.notifier.as_ref().and_then(Platform::fd)
+    }
+
+    /// Gather device nodes found within `grace_period` of each other that
+    /// belong to the same physical device into one [`Composite`].
+    ///
+    /// This lets a multi-function device (for example, a USB audio interface
+    /// with both PCM and MIDI nodes) be initialized all at once.  Use
+    /// [`Composite::primary()`] to get the node to open when only one is
+    /// needed, so a camera's metadata node isn't opened by mistake.
+    pub fn batch(self, grace_period: Duration) -> Batcher {
+        Batcher::new(self, grace_period)
     }
 }
 
 impl Notify for Searcher {
     type Event = Found;
 
-    fn poll_next(mut self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Found> {
-        let Some(ref mut notifier) = self.0.get_mut() else { return Pending };
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Found> {
+        let this = self.get_mut();
+        let Ready(mut found) = this.poll_found(task) else {
+            return Pending;
+        };
+
+        if let Some(ref aliases) = this.aliases {
+            // Binding to a port takes priority, to tell identical devices apart
+            found.alias = found
+                .port()
+                .and_then(|port| aliases.get(&port))
+                .or_else(|| aliases.get(&found.id()));
+        }
+
+        Ready(found)
+    }
+}
+
+impl Searcher {
+    /// Keep the paths of devices removed, if they're being tracked
+    fn take_removed(&mut self) {
+        let Some(notifier) = self.notifier.as_mut() else {
+            return;
+        };
+        let mut removed = Platform::take_removed(notifier);
+
+        if let Some(ref mut debounce) = self.debounce {
+            debounce.remove(&removed);
+        }
+
+        if let Some(ref mut stable) = self.stable {
+            stable.remove(&mut removed);
+        }
 
-        Pin::new(notifier).poll_next(task)
+        if let Some(ref mut paths) = self.removed {
+            paths.extend(removed);
+        }
+    }
+
+    /// Poll for the next device found that passes the filters
+    fn poll_accepted(&mut self, task: &mut Task<'_>) -> Poll<Found> {
+        loop {
+            let mut found = match self.restored.pop_front() {
+                Some(found) => found,
+                None => {
+                    let Some(notifier) = self.notifier.as_mut() else {
+                        return Pending;
+                    };
+                    let poll = Pin::new(notifier).poll_next(task);
+
+                    self.take_removed();
+
+                    let Ready(found) = poll else {
+                        return Pending;
+                    };
+
+                    found
+                }
+            };
+
+            found.kind = self.target.kind();
+
+            if self.filters.accepts(&found) {
+                return Ready(found);
+            }
+        }
+    }
+
+    /// Poll for the next device found that passes the filters, once events
+    /// for its path have settled
+    fn poll_settled(&mut self, task: &mut Task<'_>) -> Poll<Found> {
+        if self.debounce.is_none() {
+            return self.poll_accepted(task);
+        }
+
+        while let Ready(found) = self.poll_accepted(task) {
+            if let Some(ref mut debounce) = self.debounce {
+                debounce.add(found);
+            }
+        }
+
+        let debounce = self.debounce.as_mut();
+
+        debounce.map_or(Pending, |debounce| Pin::new(debounce).poll_next(task))
+    }
+
+    /// Poll for the next device found that has settled, once its stable path
+    /// exists (when preferred)
+    fn poll_linked(&mut self, task: &mut Task<'_>) -> Poll<Found> {
+        if self.stable.is_none() {
+            return self.poll_settled(task);
+        }
+
+        while let Ready(found) = self.poll_settled(task) {
+            if let Some(ref mut stable) = self.stable {
+                stable.add(found);
+            }
+        }
+
+        let stable = self.stable.as_mut();
+
+        stable.map_or(Pending, |stable| Pin::new(stable).poll_next(task))
+    }
+
+    /// Poll for the next device found, with the limit applied
+    fn poll_found(&mut self, task: &mut Task<'_>) -> Poll<Found> {
+        if self.limit.is_none() {
+            return self.poll_linked(task);
+        }
+
+        while let Ready(found) = self.poll_linked(task) {
+            self.parked.push_back(found);
+        }
+
+        if self.parked.is_empty() {
+            return Pending;
+        }
+
+        let limit = self.limit.as_ref();
+        let Some(permit) = limit.and_then(|limit| limit.acquire(task.waker()))
+        else {
+            return Pending;
+        };
+        let mut found = self.parked.pop_front().unwrap();
+
+        found.permit = Some(permit);
+
+        Ready(found)
     }
 }
 
 /// Device found by the [`Searcher`] notifier.
-pub struct Found(Cell<String>);
+pub struct Found {
+    /// Device node, which everything about the device is looked up from
+    path: PathBuf,
+    /// Path the device is reported by, if it has a stable one and it's
+    /// preferred
+    stable: Option<Box<Path>>,
+    /// Released once connected or dropped
+    permit: Option<Permit>,
+    alias: Option<String>,
+    /// Kind of searcher the device was found by
+    kind: Option<Kind>,
+    /// When the event the device was found from was read
+    timestamp: Instant,
+}
+
+impl AsRef<Path> for Found {
+    fn as_ref(&self) -> &Path {
+        self.path()
+    }
+}
 
 impl fmt::Debug for Found {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let path = self.0.take();
-
-        f.debug_struct("Found").field("path", &path).finish()?;
-        self.0.set(path);
-
-        Ok(())
+        f.debug_struct("Found").field("path", &self.path).finish()
     }
 }
 
 impl Found {
+    fn new(path: PathBuf) -> Self {
+        let stable = None;
+        let permit = None;
+        let alias = None;
+        // Set by the searcher, once the device is delivered
+        let kind = None;
+        // Replaced by the platform, if the event was read earlier
+        let timestamp = Instant::now();
+
+        Self {
+            path,
+            stable,
+            permit,
+            alias,
+            kind,
+            timestamp,
+        }
+    }
+
+    /// Run a closure with the path of the device
+    fn with_path<T>(&self, f: impl FnOnce(&Path) -> T) -> T {
+        f(&self.path)
+    }
+
+    /// Try to open the device, returning the access mode granted
+    fn open_with(self, events: Events) -> Result<(Device, Access), Error> {
+        self.connect_with(ConnectOptions::with_events(events))
+    }
+
+    /// Connect to device with custom options, see [`ConnectOptions`].
+    pub fn connect_with(
+        self,
+        options: ConnectOptions,
+    ) -> Result<(Device, Access), Error> {
+        Platform::open(self, &options)
+            .map(|device| (device, options.access()))
+            .map_err(|(found, error)| Error::new(found, error))
+    }
+
+    /// Open the device node with custom options, without connecting to it.
+    ///
+    /// This is for when devices are watched by something other than
+    /// [`Device`], like another async runtime's reactor.  Nothing is watched,
+    /// so the events in the options only choose the access mode.
+    ///
+    /// ```rust, no_run
+    /// use lookit::{ConnectOptions, Searcher};
+    /// use pasts::prelude::*;
+    ///
+    /// #[async_main::async_main]
+    /// async fn main(_spawner: impl async_main::Spawn) {
+    ///     let found = Searcher::with_input().next().await;
+    ///
+    ///     if let Ok(fd) = found.open_fd(ConnectOptions::new().input()) {
+    ///         dbg!(fd);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(any(unix, target_os = "wasi"))]
+    pub fn open_fd(self, options: ConnectOptions) -> Result<OwnedFd, Error> {
+        Platform::open_fd(self, &options)
+            .map_err(|(found, error)| Error::new(found, error))
+    }
+
     /// Connect to device (input + output)
-    pub fn connect(self) -> Result<Device, Found> {
-        Platform::open(self, Events::All())
+    pub fn connect(self) -> Result<(Device, Access), Error> {
+        self.open_with(Events::All())
+    }
+
+    /// Connect to a camera (input + output), along with its capture metadata
+    /// (the format and capabilities the driver reports), for handing off to
+    /// a capture library.
+    ///
+    /// Fails with [`ErrorKind::Unsupported`](io::ErrorKind::Unsupported) if
+    /// the device can't capture video, which is always the case on platforms
+    /// other than Linux.
+    ///
+    /// ```rust, no_run
+    /// use lookit::{Feature, Searcher};
+    /// use pasts::prelude::*;
+    ///
+    /// #[async_main::async_main]
+    /// async fn main(_spawner: impl async_main::Spawn) {
+    ///     let mut searcher =
+    ///         Searcher::with_camera().require(Feature::Capture);
+    ///     let found = searcher.next().await;
+    ///
+    ///     if let Ok(camera) = found.connect_camera() {
+    ///         dbg!(camera.info().format);
+    ///     }
+    /// }
+    /// ```
+    pub fn connect_camera(self) -> Result<Camera, Error> {
+        let info = match self.with_path(Platform::video_info) {
+            Ok(info) => info,
+            Err(error) => return Err(Error::new(self, error)),
+        };
+        let path = self.path().to_path_buf();
+        let (device, access) = self.connect()?;
+
+        Ok(Camera::new(device, access, path, info))
     }
 
     /// Connect to device (input only)
-    pub fn connect_input(self) -> Result<Device, Found> {
-        Platform::open(self, Events::Read())
+    pub fn connect_input(self) -> Result<(Device, Access), Error> {
+        self.open_with(Events::Read())
     }
 
     /// Connect to device (output only)
-    pub fn connect_output(self) -> Result<Device, Found> {
-        Platform::open(self, Events::Write())
+    pub fn connect_output(self) -> Result<(Device, Access), Error> {
+        self.open_with(Events::Write())
+    }
+
+    /// Connect to device with the widest access mode permitted, trying input +
+    /// output, then input only, then output only.
+    ///
+    /// Returns the device along with the access mode that was obtained.
+    pub fn connect_best(self) -> Result<(Device, Access), Error> {
+        self.connect()
+            .or_else(|e| e.into_found().connect_input())
+            .or_else(|e| e.into_found().connect_output())
+    }
+
+    /// Get the platform path of the device (for example, `/dev/video2`).
+    ///
+    /// Devices without device nodes (like network interfaces) are found by
+    /// their sysfs directory on Linux.  This is the
+    /// [stable path](Found::stable_path) instead when the searcher was set to
+    /// [prefer them](Searcher::stable_paths).
+    pub fn path(&self) -> &Path {
+        self.stable.as_deref().unwrap_or(&self.path)
+    }
+
+    /// Get a stable identifier for the physical device, that stays the same
+    /// when it's reconnected (for example, `usb:046d:082d:A1B2C3D4` for a USB
+    /// device with a serial number).
+    ///
+    /// Falls back to the device node path where there's nothing more stable.
+    pub fn id(&self) -> String {
+        self.with_path(|path| {
+            Platform::id(path)
+                .unwrap_or_else(|| path.to_string_lossy().into_owned())
+        })
+    }
+
+    /// Get a path to the device that stays the same when it's reconnected,
+    /// for saving which device was chosen (for example,
+    /// `/dev/serial/by-id/usb-FTDI_FT232R_USB_UART_A1B2C3D4-if00-port0`).
+    ///
+    /// On Linux, this is a by-id symlink created by udev (in `/dev/serial/`,
+    /// `/dev/v4l/`, `/dev/input/` or `/dev/disk/`).  Returns `None` when there
+    /// isn't one.
+    pub fn stable_path(&self) -> Option<PathBuf> {
+        self.stable
+            .as_deref()
+            .map(Path::to_path_buf)
+            .or_else(|| self.with_path(Platform::stable_path))
+    }
+
+    /// Get an identifier for the USB port the device is plugged into (for
+    /// example, `usb-port:1-2.3`), or `None` if it's not a USB device.
+    ///
+    /// Unlike [`Found::id()`], this tells identical devices apart, so it can be
+    /// used in [`Aliases`] to bind roles (like "Player 1") to ports.
+    pub fn port(&self) -> Option<String> {
+        self.with_path(Platform::port)
+    }
+
+    /// Get a human-readable name for the device (for example,
+    /// `HD Pro Webcam C920`), or `None` if it doesn't have one.
+    ///
+    /// On Linux, this is the name the kernel driver gives the device, the
+    /// sound card's name for audio and MIDI devices, or else the USB product
+    /// name.
+    pub fn name(&self) -> Option<String> {
+        self.with_path(Platform::name)
+    }
+
+    /// Get the USB vendor ID, product ID and serial number of the physical
+    /// device, or `None` if it's not a USB device.
+    pub fn info(&self) -> Option<UsbInfo> {
+        self.with_path(Platform::usb_info)
+    }
+
+    /// Get the bus, kernel driver, device number and subsystem of the device.
+    ///
+    /// These are read from the system when this is called, so they let a
+    /// device be inspected without finding its sysfs directory.
+    pub fn device_info(&self) -> DeviceInfo {
+        self.with_path(Platform::device_info)
+    }
+
+    /// Get the kind of searcher that found the device (for example,
+    /// [`Kind::Camera`] for [`Searcher::with_camera()`]), named with
+    /// [`Kind::name()`].
+    ///
+    /// This tells devices apart when handling ones from multiple searchers
+    /// together.  Returns `None` for devices found with
+    /// [`Searcher::with_path()`], which aren't of any kind.
+    pub fn kind(&self) -> Option<Kind> {
+        self.kind
+    }
+
+    /// Get the monotonic time of when the device was found.
+    ///
+    /// On Linux, this is when the inotify event was read for device files
+    /// created after the searcher started, so it can be used to measure how
+    /// long a device takes to be ready after it's plugged in.  Otherwise, it's
+    /// when the device was noticed (such as when the directory was listed).
+    ///
+    /// [`Merged`] uses this to deliver devices in the order they were found.
+    pub fn timestamp(&self) -> Instant {
+        self.timestamp
+    }
+
+    /// Get the user-friendly label for the device, from the
+    /// [`Aliases`] set with [`Searcher::aliases()`].
+    ///
+    /// Labels for the device's [port](Found::port) take priority over labels
+    /// for its [id](Found::id).
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+
+    /// Check if this is a partition of a [storage](Searcher::with_storage)
+    /// device, rather than a whole disk.
+    ///
+    /// Only detected on Linux, returns `false` elsewhere.
+    pub fn is_partition(&self) -> bool {
+        self.with_path(Platform::partition)
+    }
+
+    /// Check if this is the ALSA sequencer found by a
+    /// [MIDI](Searcher::with_midi) searcher (`/dev/snd/seq`), rather than a
+    /// raw MIDI device.
+    ///
+    /// The sequencer gives access to every MIDI port through one node, instead
+    /// of to one device directly.
+    pub fn is_sequencer(&self) -> bool {
+        self.with_path(|path| path == Path::new("/dev/snd/seq"))
+    }
+
+    /// Get the type of node found by an [NVMe](Searcher::with_nvme) searcher,
+    /// or `None` if it's not an NVMe controller or namespace.
+    pub fn nvme_node(&self) -> Option<NvmeNode> {
+        self.with_path(|path| {
+            let name = path.file_name()?.to_str()?.strip_prefix("nvme")?;
+            let digit = |c: char| c.is_ascii_digit();
+            let rest = name.trim_start_matches(digit);
+
+            if rest.len() == name.len() {
+                return None;
+            }
+
+            if rest.is_empty() {
+                return Some(NvmeNode::Controller);
+            }
+
+            // Namespaces are "n1" on Linux, and "ns1" on FreeBSD
+            let number = rest.strip_prefix("ns").or(rest.strip_prefix('n'))?;
+
+            if number.is_empty() || !number.chars().all(digit) {
+                return None;
+            }
+
+            Some(NvmeNode::Namespace)
+        })
+    }
+
+    /// Watch for the device to be unplugged, which keeps working after it's
+    /// connected to, see [`watch_removal()`].
+    pub fn removal(&self) -> Removal {
+        watch_removal(self.path())
+    }
+
+    /// Connect to device (input + output), waiting while another process has
+    /// it open exclusively.
+    ///
+    /// Some devices (like MIDI ports and cameras) can only be opened by one
+    /// process at a time; the returned future retries whenever the device is
+    /// closed by its holder.
+    pub fn waitlist(self) -> Waitlist {
+        Waitlist::new(self)
     }
 }
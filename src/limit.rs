@@ -0,0 +1,119 @@
+use std::{
+    sync::{Arc, Mutex},
+    task::Waker,
+};
+
+/// Shared count of [`Found`](crate::Found)s that haven't been connected yet
+#[derive(Debug, Default)]
+struct State {
+    outstanding: usize,
+    waker: Option<Waker>,
+}
+
+/// Cap on outstanding [`Found`](crate::Found)s for one searcher
+#[derive(Debug)]
+pub(crate) struct Limit {
+    max: usize,
+    state: Arc<Mutex<State>>,
+}
+
+impl Limit {
+    pub(crate) fn new(max: usize) -> Self {
+        let state = Arc::new(Mutex::new(State::default()));
+
+        Self { max, state }
+    }
+
+    /// Try to take a permit, registering the waker if there are none left
+    pub(crate) fn acquire(&self, waker: &Waker) -> Option<Permit> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.outstanding >= self.max {
+            state.waker = Some(waker.clone());
+            return None;
+        }
+
+        state.outstanding += 1;
+
+        Some(Permit(self.state.clone()))
+    }
+}
+
+/// Held by a [`Found`](crate::Found) until it's connected or dropped
+#[derive(Debug)]
+pub(crate) struct Permit(Arc<Mutex<State>>);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut state = self.0.lock().unwrap();
+
+        state.outstanding -= 1;
+
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::{
+        fs,
+        sync::atomic::{AtomicUsize, Ordering},
+        task::Wake,
+    };
+
+    use super::*;
+    use crate::SearcherBuilder;
+
+    /// Counts how many times it's woken
+    #[derive(Default)]
+    struct Count(AtomicUsize);
+
+    impl Wake for Count {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn freed_slot_wakes() {
+        let count = Arc::new(Count::default());
+        let waker = count.clone().into();
+        let limit = Limit::new(1);
+        let permit = limit.acquire(&waker).unwrap();
+
+        assert!(limit.acquire(&waker).is_none());
+        drop(permit);
+        assert_eq!(count.0.load(Ordering::SeqCst), 1);
+        assert!(limit.acquire(&waker).is_some());
+    }
+
+    #[test]
+    fn parked_released() {
+        let dir = std::env::temp_dir()
+            .join(format!("lookit-limit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("dev0"), "").unwrap();
+        fs::write(dir.join("dev1"), "").unwrap();
+
+        let path = format!("{}/", dir.display());
+        let mut searcher = SearcherBuilder::with_path(path, ["dev"])
+            .watched(false)
+            .build()
+            .limit(1);
+        let first = searcher.try_next().unwrap();
+
+        // Parked until the first one's dropped
+        assert!(searcher.try_next().is_none());
+
+        let mut paths = vec![first.path().to_path_buf()];
+
+        drop(first);
+        paths.push(searcher.try_next().unwrap().path().to_path_buf());
+        paths.sort();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(paths, [dir.join("dev0"), dir.join("dev1")]);
+    }
+}
@@ -1,19 +1,39 @@
 use std::{
-    ffi::CString,
+    collections::VecDeque,
+    ffi::{CString, OsStr},
     fs::{OpenOptions, ReadDir},
-    io::Read,
-    mem::{self, MaybeUninit},
+    io::{self, Read},
+    mem::{self, size_of, MaybeUninit},
     os::{
-        fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
-        raw::{c_char, c_int, c_uint, c_ulong},
-        unix::fs::OpenOptionsExt,
+        fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd},
+        raw::{c_char, c_int, c_uint, c_ulong, c_void},
+        unix::{ffi::OsStrExt, fs::OpenOptionsExt},
     },
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
 };
 
 use pasts::prelude::*;
 use smelling_salts::Watch;
 
-use crate::{Device, Events, Found, Interface, Kind, Platform};
+use self::{
+    hotplug::Hotplug, listener::Listener, rtnetlink::Rtnetlink, uevent::Uevent,
+};
+use crate::{
+    devfs,
+    scan::{self, Scanner},
+    timer::Timer,
+    Attempt, Backend, ConnectOptions, Device, DeviceInfo, Dir, Events, Feature,
+    Found, Interface, Kind, Platform, Restriction, SearcherBuilder, Snapshot,
+    Target, UsbInfo, VideoInfo,
+};
+
+mod hotplug;
+mod listener;
+mod rtnetlink;
+mod support;
+mod sysfs;
+mod uevent;
 
 // Inotify
 
@@ -30,152 +50,777 @@ struct InotifyEv {
     len: u32,
 }
 
+// https://github.com/torvalds/linux/blob/dbad9ce9397ef7f891b4ff44bad694add673c1a1/include/uapi/linux/inotify.h#L29
+
+const IN_NONBLOCK: c_int = 0o4000;
+const IN_CLOEXEC: c_int = 0o2000000;
+
+const IN_ATTRIB: u32 = 0x004;
+const IN_CLOSE_WRITE: u32 = 0x008;
+const IN_CLOSE_NOWRITE: u32 = 0x010;
+const IN_CREATE: u32 = 0x100;
+const IN_DELETE: u32 = 0x200;
+const IN_DELETE_SELF: u32 = 0x400;
+const IN_IGNORED: u32 = 0x8000;
+const IN_ISDIR: u32 = 0x40000000;
+
 extern "C" {
     fn inotify_init1(flags: c_int) -> RawFd;
     fn inotify_add_watch(fd: RawFd, path: *const c_char, mask: u32) -> c_int;
+    fn dup(fd: RawFd) -> RawFd;
+    fn fcntl(fd: RawFd, cmd: c_int, ...) -> c_int;
+    fn access(path: *const c_char, mode: c_int) -> c_int;
+}
+
+// https://github.com/torvalds/linux/blob/dbad9ce9397ef7f891b4ff44bad694add673c1a1/include/uapi/asm-generic/fcntl.h
+
+const O_EXCL: c_int = 0o200;
+const O_NONBLOCK: c_int = 0o4000;
+const F_SETFD: c_int = 2;
+const FD_CLOEXEC: c_int = 1;
+
+const R_OK: c_int = 4;
+const W_OK: c_int = 2;
+
+/// Directories of symlinks to device nodes, named after their physical device
+const BY_ID: &[&str] = &[
+    "/dev/serial/by-id/",
+    "/dev/v4l/by-id/",
+    "/dev/input/by-id/",
+    "/dev/disk/by-id/",
+];
+
+/// Where udev keeps what it knows about each device node, once it's set up
+const UDEV_DATA: &str = "/run/udev/data/";
+
+/// How long to wait for udev to let a new device file be opened, before
+/// finding it anyway
+const UNREADY: Duration = Duration::from_secs(1);
+
+/// Check if a path can be opened for reading or writing by this process
+fn is_accessible(path: &Path) -> bool {
+    let Ok(path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+
+    unsafe {
+        access(path.as_ptr(), R_OK) == 0 || access(path.as_ptr(), W_OK) == 0
+    }
+}
+
+/// Create a new inotify instance
+fn inotify_init() -> io::Result<OwnedFd> {
+    let listen = unsafe { inotify_init1(IN_NONBLOCK | IN_CLOEXEC) };
+    // Fails when out of memory, or the instance limit has been reached
+    if listen == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(unsafe { OwnedFd::from_raw_fd(listen) })
+}
+
+/// Add a path to an inotify instance's watch list, returning the descriptor
+fn inotify_watch(listen: RawFd, path: &Path, mask: u32) -> io::Result<c_int> {
+    let path = CString::new(path.as_os_str().as_bytes())?;
+    let wd =
+        unsafe { inotify_add_watch(listen, path.as_c_str().as_ptr(), mask) };
+
+    if wd == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(wd)
+}
+
+/// Check if an error is from a sandbox blocking an operation
+fn is_blocked(error: &io::Error) -> bool {
+    // EPERM, EACCES, ENOSYS, EPROTONOSUPPORT, EAFNOSUPPORT
+    matches!(error.raw_os_error(), Some(1 | 13 | 38 | 93 | 97))
+}
+
+/// Duplicate a file descriptor, leaving the duplicate open across `exec()`
+fn dup_inherited(fd: &impl AsRawFd) -> Option<RawFd> {
+    let fd = unsafe { dup(fd.as_raw_fd()) };
+
+    (fd != -1).then_some(fd)
+}
+
+/// Take ownership of a file descriptor left open across `exec()`
+///
+/// # Safety
+///
+/// `fd` must not be owned by anything else in this process.
+unsafe fn adopt(fd: RawFd) -> Option<OwnedFd> {
+    // Fails if the file descriptor isn't open
+    if unsafe { fcntl(fd, F_SETFD, FD_CLOEXEC) } == -1 {
+        return None;
+    }
+
+    Some(unsafe { OwnedFd::from_raw_fd(fd) })
 }
 
 // Lookit interface
 
 impl Interface for Platform {
+    type Closed = Closed;
     type Searcher = Searcher;
 
-    fn searcher(kind: Kind) -> Option<Searcher> {
-        Searcher::new(kind)
+    fn searcher(
+        builder: &SearcherBuilder,
+        attempts: &mut Vec<Attempt>,
+    ) -> Option<Searcher> {
+        Searcher::new(builder, attempts)
+    }
+
+    fn backend(searcher: &Searcher) -> Backend {
+        searcher.backend()
     }
 
-    fn open(found: Found, events: Events) -> Result<Device, Found> {
+    fn restrictions(attempts: &[Attempt]) -> Vec<Restriction> {
+        let blocked = |backend| {
+            attempts.iter().any(|attempt| {
+                attempt.backend() == backend && is_blocked(attempt.error())
+            })
+        };
+        let mut restrictions = Vec::new();
+
+        if std::fs::read_dir("/dev").is_err() {
+            restrictions.push(Restriction::Devices);
+        }
+
+        if blocked(Backend::Inotify) {
+            restrictions.push(Restriction::Inotify);
+        }
+
+        if blocked(Backend::Uevent) || blocked(Backend::Rtnetlink) {
+            restrictions.push(Restriction::Netlink);
+        }
+
+        if std::fs::read_dir("/sys/dev").is_err() {
+            restrictions.push(Restriction::Sysfs);
+        }
+
+        restrictions
+    }
+
+    fn open(
+        found: Found,
+        options: &ConnectOptions,
+    ) -> Result<Device, (Found, io::Error)> {
         use Events::*;
-        let device = match events {
-            Read() => Device::new(found.open_r()?, Watch::INPUT),
-            Write() => Device::new(found.open_w()?, Watch::OUTPUT),
-            All() => Device::new(found.open()?, Watch::INPUT.output()),
+        let watch = match options.events {
+            Read() => Watch::INPUT,
+            Write() => Watch::OUTPUT,
+            All() => Watch::INPUT.output(),
         };
 
-        Ok(device)
+        Ok(Device::new(Self::open_fd(found, options)?, watch))
     }
-}
 
-impl Found {
-    /// Open read and write non-blocking device
-    fn open_flags(mut self, read: bool, write: bool) -> Result<OwnedFd, Self> {
-        if let Ok(file) = OpenOptions::new()
+    fn open_fd(
+        found: Found,
+        options: &ConnectOptions,
+    ) -> Result<OwnedFd, (Found, io::Error)> {
+        use Events::*;
+        let (read, write) = match options.events {
+            Read() => (true, false),
+            Write() => (false, true),
+            All() => (true, true),
+        };
+        let mut flags = options.flags;
+
+        if options.nonblocking {
+            flags |= O_NONBLOCK;
+        }
+
+        if options.exclusive {
+            flags |= O_EXCL;
+        }
+
+        let fd: OwnedFd = match OpenOptions::new()
             .read(read)
             .write(write)
-            .custom_flags(2048)
-            .open(self.0.get_mut())
+            .append(write && options.append)
+            .custom_flags(flags)
+            .open(&found.path)
         {
-            Ok(file.into())
-        } else {
-            Err(self)
+            Ok(file) => file.into(),
+            Err(error) => return Err((found, error)),
+        };
+
+        // Files are always opened with `O_CLOEXEC`
+        if !options.cloexec
+            && unsafe { fcntl(fd.as_raw_fd(), F_SETFD, 0) } == -1
+        {
+            return Err((found, io::Error::last_os_error()));
+        }
+
+        Ok(fd)
+    }
+
+    fn parent(path: &Path) -> Option<String> {
+        sysfs::parent(path)?.into_os_string().into_string().ok()
+    }
+
+    fn id(path: &Path) -> Option<String> {
+        sysfs::id(path)
+    }
+
+    fn stable_path(path: &Path) -> Option<PathBuf> {
+        let name = path.file_name()?;
+        let node = std::fs::canonicalize(path).ok()?;
+        let mut links: Vec<PathBuf> = BY_ID
+            .iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            // Only links to a node of the same name need resolving
+            .filter(|link| {
+                std::fs::read_link(link)
+                    .map_or(false, |target| target.file_name() == Some(name))
+            })
+            .filter(|link| {
+                std::fs::canonicalize(link).map_or(false, |link| link == node)
+            })
+            .collect();
+
+        // Disks have a link for each kind of ID, so pick one consistently
+        links.sort();
+        links.into_iter().next()
+    }
+
+    fn settled(path: &Path) -> bool {
+        // Without udev, no links are coming
+        if !Path::new(UDEV_DATA).exists() {
+            return true;
+        }
+
+        let Some((kind, major, minor)) = sysfs::number(path) else {
+            return true;
+        };
+        let kind = &kind[..1];
+
+        // Written once udev has finished with the device, links included
+        Path::new(UDEV_DATA)
+            .join(format!("{kind}{major}:{minor}"))
+            .exists()
+    }
+
+    fn port(path: &Path) -> Option<String> {
+        sysfs::port(path)
+    }
+
+    fn name(path: &Path) -> Option<String> {
+        sysfs::name(path)
+    }
+
+    fn partition(path: &Path) -> bool {
+        sysfs::node(path).map_or(false, |node| node.join("partition").exists())
+    }
+
+    fn index(path: &Path) -> Option<u32> {
+        sysfs::index(path)
+    }
+
+    fn usb_class(path: &Path, class: u8) -> bool {
+        sysfs::usb_class(path, class)
+    }
+
+    fn usb_vendor(path: &Path) -> Option<u16> {
+        sysfs::usb_vendor(path)
+    }
+
+    fn usb_info(path: &Path) -> Option<UsbInfo> {
+        sysfs::usb_info(path)
+    }
+
+    fn device_info(path: &Path) -> DeviceInfo {
+        sysfs::device_info(path)
+    }
+
+    fn hid_usage(path: &Path, pages: &[u16]) -> bool {
+        sysfs::hid_usage(path, pages)
+    }
+
+    fn supports(path: &Path, feature: Feature) -> bool {
+        support::supports(path, feature)
+    }
+
+    fn video_info(path: &Path) -> io::Result<VideoInfo> {
+        support::video_info(path)
+    }
+
+    fn fd(searcher: &Searcher) -> Option<BorrowedFd<'_>> {
+        searcher.fd()
+    }
+
+    fn take_removed(searcher: &mut Searcher) -> VecDeque<PathBuf> {
+        searcher.take_removed()
+    }
+
+    fn watch_close(path: &Path) -> Option<Closed> {
+        let listen = inotify_init().ok()?;
+
+        let mask = IN_CLOSE_WRITE | IN_CLOSE_NOWRITE;
+
+        inotify_watch(listen.as_raw_fd(), path, mask).ok()?;
+
+        Some(Closed(Device::new(listen, Watch::INPUT)))
+    }
+
+    fn watch_removal(path: &Path) -> Option<Closed> {
+        let listen = inotify_init().ok()?;
+
+        // Unlinking changes the link count, while the node's still open
+        let mask = IN_ATTRIB | IN_DELETE_SELF;
+
+        inotify_watch(listen.as_raw_fd(), path, mask).ok()?;
+
+        Some(Closed(Device::new(listen, Watch::INPUT)))
+    }
+
+    fn enumerate(kind: Kind) -> Vec<PathBuf> {
+        // List the same directory a searcher would
+        let dirs = dirs(&Target::Kind(kind));
+        let dir = dirs
+            .iter()
+            .find(|dir| std::fs::read_dir(&*dir.path).is_ok());
+        let Some(dir) = dir else {
+            return Vec::new();
+        };
+
+        // Connectors stay in sysfs while nothing is plugged into them
+        if kind == Kind::Display {
+            return hotplug::connected()
+                .into_iter()
+                .filter(|path| dir.matches(path))
+                .collect();
         }
+
+        scan::list(std::slice::from_ref(dir)).into_iter().collect()
     }
 
-    /// Open read and write non-blocking
-    fn open(self) -> Result<OwnedFd, Self> {
-        self.open_flags(true, true)
+    fn save(searcher: Searcher, snapshot: &mut Snapshot) {
+        searcher.save(snapshot);
     }
 
-    /// Open read-only non-blocking
-    fn open_r(self) -> Result<OwnedFd, Self> {
-        self.open_flags(true, false)
+    unsafe fn restore(snapshot: &Snapshot) -> Option<Searcher> {
+        Searcher::restore(snapshot)
     }
+}
+
+/// Notifier for a device node being closed (or changed, when watching for its
+/// removal)
+#[derive(Debug)]
+pub(super) struct Closed(Device);
 
-    /// Open write-only non-blocking
-    fn open_w(self) -> Result<OwnedFd, Self> {
-        self.open_flags(false, true)
+impl Notify for Closed {
+    type Event = ();
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll {
+        let this = self.get_mut();
+        let mut closed = false;
+
+        while let Ready(()) = Pin::new(&mut this.0).poll_next(task) {
+            let mut buffer = [0; 256];
+
+            while let Ok(1..) = this.0.read(&mut buffer) {}
+            closed = true;
+        }
+
+        if closed {
+            Ready(())
+        } else {
+            Pending
+        }
     }
 }
 
 // Searcher
 
+/// Directories where device nodes for a target are found, to try in order
+fn dirs(target: &Target) -> Vec<Dir> {
+    match target {
+        Target::Kind(kind) => devfs::dirs(*kind),
+        Target::Custom(dir) => vec![dir.clone()],
+    }
+}
+
+/// How often device directories get rescanned, when they can't be watched
+const INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug)]
 pub(super) struct Searcher {
-    path: &'static str,
-    prefix: &'static str,
-    device: Device,
-    read_dir: std::io::Result<ReadDir>,
-    buffer: Vec<u8>,
+    dir: Dir,
+    /// Directories still to be listed, for the initial search
+    read_dirs: Vec<ReadDir>,
+    source: Source,
+}
+
+/// Where new devices are found from
+#[derive(Debug)]
+enum Source {
+    Inotify(Inotify),
+    Uevent(Uevent),
+    Rtnetlink(Rtnetlink),
+    Hotplug(Hotplug),
+    Scan(Scanner),
 }
 
 impl Searcher {
-    fn new(kind: Kind) -> Option<Self> {
-        use Kind::*;
-        match kind {
-            Input() => Self::with("/dev/input/", "event"),
-            Audio() => Self::with("/dev/snd/", "pcm"),
-            Midi() => Self::with("/dev/snd/", "midi")
-                .or_else(|| Self::with("/dev/", "midi")),
-            Camera() => Self::with("/dev/", "video"),
-        }
-    }
-
-    fn with(path: &'static str, prefix: &'static str) -> Option<Self> {
-        // https://github.com/torvalds/linux/blob/dbad9ce9397ef7f891b4ff44bad694add673c1a1/include/uapi/linux/inotify.h#L29
-
-        const IN_NONBLOCK: c_int = 0o4000;
-        const IN_CLOEXEC: c_int = 0o2000000;
-
-        const IN_ATTRIB: u32 = 0x004;
-        const IN_CREATE: u32 = 0x100;
-        const IN_DELETE: u32 = 0x200;
-
-        let listen = unsafe { inotify_init1(IN_NONBLOCK | IN_CLOEXEC) };
-        assert_ne!(-1, listen); // The only way this fails is some kind of OOM
-        let listen = unsafe { OwnedFd::from_raw_fd(listen) };
-
-        let dir = CString::new(path).unwrap();
-        if unsafe {
-            inotify_add_watch(
-                listen.as_raw_fd(),
-                dir.as_c_str().as_ptr(),
-                IN_ATTRIB | IN_CREATE | IN_DELETE,
-            )
-        } == -1
-        {
+    fn new(
+        builder: &SearcherBuilder,
+        attempts: &mut Vec<Attempt>,
+    ) -> Option<Self> {
+        let mut searcher = Self::choose(builder, attempts)?;
+
+        if !builder.initial {
+            searcher.skip_initial();
+        }
+
+        Some(searcher)
+    }
+
+    /// Create a searcher with the first backend that can be used
+    fn choose(
+        builder: &SearcherBuilder,
+        attempts: &mut Vec<Attempt>,
+    ) -> Option<Self> {
+        let target = &builder.target;
+        let dirs = dirs(target);
+
+        // Network interfaces are best found with rtnetlink
+        let network = matches!(
+            target,
+            Target::Kind(Kind::Network) | Target::Kind(Kind::Can),
+        );
+
+        if network && builder.allows(Backend::Rtnetlink) {
+            match Rtnetlink::new(builder.watched) {
+                Ok(rtnetlink) => {
+                    let source = Source::Rtnetlink(rtnetlink);
+
+                    return Some(Self::with(dirs[0].clone(), source));
+                }
+                Err(e) => {
+                    attempts.push(Attempt::new(Backend::Rtnetlink, None, e))
+                }
+            }
+        }
+
+        // Displays can only be found from hotplug uevents, since connectors
+        // stay in sysfs while nothing is plugged into them
+        if let Target::Kind(Kind::Display) = target {
+            if !builder.allows(Backend::Uevent) {
+                return None;
+            }
+
+            match Hotplug::new(builder.watched) {
+                Ok(hotplug) => {
+                    let mut searcher =
+                        Self::with(dirs[0].clone(), Source::Hotplug(hotplug));
+
+                    // Hotplug does its own initial search
+                    searcher.read_dirs.clear();
+
+                    return Some(searcher);
+                }
+                Err(e) => {
+                    attempts.push(Attempt::new(Backend::Uevent, None, e));
+
+                    return None;
+                }
+            }
+        }
+
+        // Try each backend in order of preference
+        if builder.allows(Backend::Inotify) {
+            for dir in dirs.iter() {
+                if dir.path.starts_with("/sys/") {
+                    let error = io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "sysfs can't be watched with inotify",
+                    );
+
+                    attempts.push(Attempt::new(
+                        Backend::Inotify,
+                        Some(&*dir.path),
+                        error,
+                    ));
+                    continue;
+                }
+
+                match Inotify::new(Path::new(&*dir.path), builder.watched) {
+                    Ok(inotify) => {
+                        let source = Source::Inotify(inotify);
+
+                        return Some(Self::with(dir.clone(), source));
+                    }
+                    Err(e) => attempts.push(Attempt::new(
+                        Backend::Inotify,
+                        Some(&*dir.path),
+                        e,
+                    )),
+                }
+            }
+        }
+
+        if builder.allows(Backend::Uevent) {
+            match Uevent::new(builder.watched) {
+                Ok(uevent) => {
+                    return Some(Self::with(
+                        dirs[0].clone(),
+                        Source::Uevent(uevent),
+                    ))
+                }
+                Err(e) => attempts.push(Attempt::new(Backend::Uevent, None, e)),
+            }
+        }
+
+        if !builder.allows(Backend::Scan) {
             return None;
         }
 
-        let read_dir = std::fs::read_dir(path);
-        let device = Device::new(listen, Watch::INPUT);
-        let buffer = Vec::new();
-        let connector = Self {
-            device,
-            path,
-            prefix,
-            read_dir,
-            buffer,
+        for dir in dirs.iter() {
+            let Some(scanner) =
+                Scanner::new(vec![dir.clone()], INTERVAL, attempts)
+            else {
+                continue;
+            };
+            let mut searcher = Self::with(dir.clone(), Source::Scan(scanner));
+
+            // Scanner does its own initial scan
+            searcher.read_dirs.clear();
+
+            return Some(searcher);
+        }
+
+        None
+    }
+
+    fn with(dir: Dir, source: Source) -> Self {
+        let read_dirs = std::fs::read_dir(&*dir.path).into_iter().collect();
+
+        Self {
+            dir,
+            read_dirs,
+            source,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// See [`Interface::restore()`].
+    unsafe fn restore(snapshot: &Snapshot) -> Option<Self> {
+        let dir = dirs(&snapshot.target)
+            .into_iter()
+            .find(|dir| snapshot.dir.as_deref() == Some(&*dir.path))?;
+        let source = match snapshot.backend? {
+            Backend::Inotify => {
+                let device = Listener::new(adopt(snapshot.fd?)?, true);
+                let buffer = Vec::new();
+                let watches = snapshot.watches.clone();
+                let removed = VecDeque::new();
+                let read_at = Instant::now();
+                let unready = Vec::new();
+                let timer = Timer::default();
+
+                Source::Inotify(Inotify {
+                    device,
+                    buffer,
+                    watches,
+                    removed,
+                    read_at,
+                    unready,
+                    timer,
+                })
+            }
+            Backend::Uevent
+                if snapshot.target == Target::Kind(Kind::Display) =>
+            {
+                let fd = adopt(snapshot.fd?)?;
+                let connected = snapshot.seen.iter().cloned().collect();
+
+                Source::Hotplug(Hotplug::restore(fd, connected))
+            }
+            Backend::Uevent => {
+                Source::Uevent(Uevent::with_fd(adopt(snapshot.fd?)?, true))
+            }
+            Backend::Rtnetlink => {
+                let fd = adopt(snapshot.fd?)?;
+
+                Source::Rtnetlink(Rtnetlink::restore(fd, &snapshot.interfaces))
+            }
+            Backend::Scan => {
+                let scanner =
+                    Scanner::restore(vec![dir.clone()], INTERVAL, snapshot);
+
+                Source::Scan(scanner)
+            }
         };
+        // Devices from the initial search were already found
+        let read_dirs = Vec::new();
 
-        Some(connector)
+        Some(Self {
+            dir,
+            read_dirs,
+            source,
+        })
     }
 
-    fn find(&mut self) -> Option<Found> {
-        if self.buffer.is_empty() {
-            return None;
+    fn save(mut self, snapshot: &mut Snapshot) {
+        // Handle events that have already been read
+        if let Source::Inotify(ref mut inotify) = self.source {
+            let mut events = Vec::new();
+
+            while let Some(event) = inotify.event() {
+                events.push(event);
+            }
+
+            for (path, is_dir) in events {
+                if is_dir {
+                    self.subdir(&path);
+                } else if self.dir.matches(&path) {
+                    snapshot.pending.push(path);
+                }
+            }
+        }
+
+        // Finish the initial search, so no devices from it are missed
+        while let Some(found) = self.list() {
+            snapshot.pending.push(found.path);
         }
 
-        let begin: [u8; mem::size_of::<InotifyEv>()] = self.buffer
-            [..mem::size_of::<InotifyEv>()]
-            .try_into()
-            .unwrap();
-        let inotify_ev: InotifyEv = unsafe { mem::transmute(begin) };
-        let len = inotify_ev.len.try_into().unwrap_or(usize::MAX);
-        let bytes = &self.buffer[mem::size_of::<InotifyEv>()..][..len];
-        let bytes = bytes.split(|n| *n == b'\0').next().unwrap_or_default();
-        let filename = String::from_utf8_lossy(bytes);
+        snapshot.dir = Some(self.dir.path.to_string());
+
+        match self.source {
+            Source::Inotify(inotify) => {
+                let dir = self.dir;
+
+                snapshot.fd = dup_inherited(&inotify.device);
+                snapshot.watches = inotify.watches;
+                snapshot.pending.extend(
+                    inotify
+                        .unready
+                        .into_iter()
+                        .map(|(_, path)| path)
+                        .filter(|path| dir.matches(path)),
+                );
+            }
+            Source::Uevent(uevent) => {
+                let (device, queue) = uevent.into_parts();
+                let dir = self.dir;
+
+                snapshot.fd = dup_inherited(&device);
+                snapshot
+                    .pending
+                    .extend(queue.into_iter().filter(|path| dir.matches(path)));
+            }
+            Source::Rtnetlink(rtnetlink) => {
+                let (device, queue, known) = rtnetlink.into_parts();
+                let dir = self.dir;
+
+                snapshot.fd = dup_inherited(&device);
+                snapshot
+                    .pending
+                    .extend(queue.into_iter().filter(|path| dir.matches(path)));
+                snapshot.interfaces.extend(known);
+            }
+            Source::Hotplug(hotplug) => {
+                let (device, queue, connected) = hotplug.into_parts();
+
+                snapshot.fd = dup_inherited(&device);
+                snapshot.pending.extend(queue);
+                snapshot.seen.extend(connected);
+            }
+            Source::Scan(scanner) => scanner.save(snapshot),
+        }
+    }
 
-        if filename.starts_with(self.prefix) {
-            let path = format!("{}{filename}", self.path);
+    fn backend(&self) -> Backend {
+        match self.source {
+            Source::Inotify(_) => Backend::Inotify,
+            Source::Uevent(_) => Backend::Uevent,
+            Source::Rtnetlink(_) => Backend::Rtnetlink,
+            Source::Hotplug(_) => Backend::Uevent,
+            Source::Scan(_) => Backend::Scan,
+        }
+    }
 
-            self.buffer.drain(..mem::size_of::<InotifyEv>() + len);
+    /// Skip the devices that are already there
+    fn skip_initial(&mut self) {
+        // Subdirectories still get watched while listing
+        while self.list().is_some() {}
 
-            return Some(Found(path.into()));
+        match self.source {
+            Source::Hotplug(ref mut hotplug) => hotplug.skip_initial(),
+            Source::Scan(ref mut scanner) => scanner.skip_initial(),
+            _ => {}
         }
+    }
 
-        self.buffer.drain(..mem::size_of::<InotifyEv>() + len);
+    /// Get the file descriptor that's readable when there's more to find
+    fn fd(&self) -> Option<BorrowedFd<'_>> {
+        let device = match self.source {
+            Source::Inotify(ref inotify) => &inotify.device,
+            Source::Uevent(ref uevent) => uevent.device(),
+            Source::Rtnetlink(ref rtnetlink) => rtnetlink.device(),
+            Source::Hotplug(ref hotplug) => hotplug.device(),
+            Source::Scan(_) => return None,
+        };
+
+        Some(device.as_fd())
+    }
+
+    /// Take the paths of devices removed since last time
+    fn take_removed(&mut self) -> VecDeque<PathBuf> {
+        match self.source {
+            Source::Inotify(ref mut inotify) => mem::take(&mut inotify.removed),
+            Source::Uevent(ref mut uevent) => uevent.take_removed(),
+            Source::Rtnetlink(ref mut rtnetlink) => rtnetlink.take_removed(),
+            Source::Hotplug(ref mut hotplug) => hotplug.take_removed(),
+            Source::Scan(ref mut scanner) => scanner.take_removed(),
+        }
+    }
+
+    /// Start searching a subdirectory (when recursive)
+    fn subdir(&mut self, path: &Path) {
+        if !self.dir.searches(path) {
+            return;
+        }
+
+        if let Source::Inotify(ref mut inotify) = self.source {
+            // Subdirectory could've been removed already, may be ignored
+            let _ = inotify.watch(path);
+        }
+
+        if let Ok(read_dir) = std::fs::read_dir(path) {
+            self.read_dirs.push(read_dir);
+        }
+    }
+
+    /// Get the next device from the initial search
+    fn list(&mut self) -> Option<Found> {
+        while let Some(read_dir) = self.read_dirs.last_mut() {
+            let Some(file) = read_dir.next() else {
+                self.read_dirs.pop();
+                continue;
+            };
+            let Ok(file) = file else { continue };
+            let path = file.path();
+
+            if file.file_type().map_or(false, |kind| kind.is_dir()) {
+                self.subdir(&path);
+                continue;
+            }
+
+            if self.dir.matches(&path) {
+                return Some(Found::new(path));
+            }
+        }
 
-        self.find()
+        None
     }
 }
 
@@ -185,57 +830,260 @@ impl Notify for Searcher {
     fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Found> {
         let searcher = self.get_mut();
 
-        // Check initial device iterator.
-        if let Ok(ref mut read_dir) = searcher.read_dir {
-            for file in read_dir.flatten() {
-                let name = if let Ok(f) = file.file_name().into_string() {
-                    f
-                } else {
-                    continue;
-                };
-                if let Some(file) = file.path().to_str() {
-                    if name.starts_with(searcher.prefix) {
-                        return Ready(Found(file.to_string().into()));
+        loop {
+            // Check initial device iterator.
+            if let Some(found) = searcher.list() {
+                return Ready(found);
+            }
+
+            match searcher.source {
+                Source::Inotify(ref mut inotify) => {
+                    let Ready((path, is_dir)) = inotify.poll_event(task) else {
+                        return Pending;
+                    };
+
+                    if is_dir {
+                        searcher.subdir(&path);
+                    } else if searcher.dir.matches(&path) {
+                        let mut found = Found::new(path);
+
+                        found.timestamp = inotify.read_at;
+
+                        return Ready(found);
+                    }
+                }
+                Source::Uevent(ref mut uevent) => {
+                    let Ready(path) = uevent.poll_added(task) else {
+                        return Pending;
+                    };
+
+                    if searcher.dir.matches(&path) {
+                        return Ready(Found::new(path));
                     }
                 }
+                Source::Rtnetlink(ref mut rtnetlink) => {
+                    let Ready(path) = rtnetlink.poll_added(task) else {
+                        return Pending;
+                    };
+
+                    if searcher.dir.matches(&path) {
+                        return Ready(Found::new(path));
+                    }
+                }
+                Source::Hotplug(ref mut hotplug) => {
+                    return hotplug.poll_added(task).map(Found::new);
+                }
+                Source::Scan(ref mut scanner) => {
+                    return Pin::new(scanner).poll_next(task);
+                }
             }
-            searcher.read_dir = std::io::Result::Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "",
-            ));
         }
+    }
+}
 
-        if let Some(found) = searcher.find() {
-            return Ready(found);
-        }
+// Inotify backend
 
-        // Check for ready file descriptor.
-        while let Ready(()) = Pin::new(&mut searcher.device).poll_next(task) {
-            // https://github.com/torvalds/linux/blob/dbad9ce9397ef7f891b4ff44bad694add673c1a1/include/uapi/asm-generic/ioctls.h#L46
-            const FIONREAD: c_ulong = 0x541B;
-            extern "C" {
-                fn ioctl(fd: RawFd, req: c_ulong, len: *mut c_uint) -> c_int;
+/// Watches directories for new files
+#[derive(Debug)]
+struct Inotify {
+    device: Listener,
+    buffer: Vec<u8>,
+    /// Watch descriptors, and the directory paths they watch
+    watches: Vec<(c_int, PathBuf)>,
+    /// Paths of files removed, not yet taken
+    removed: VecDeque<PathBuf>,
+    /// When the events in the buffer were read
+    read_at: Instant,
+    /// Paths of files created before they could be opened, and when they were
+    /// created, waiting for their permissions to change
+    unready: Vec<(Instant, PathBuf)>,
+    /// Fires when it's time to give up waiting for an unready file
+    timer: Timer,
+}
+
+impl Inotify {
+    fn new(path: &Path, watched: bool) -> io::Result<Self> {
+        let listen = inotify_init()?;
+        let device = Listener::new(listen, watched);
+        let buffer = Vec::new();
+        let watches = Vec::new();
+        let removed = VecDeque::new();
+        let read_at = Instant::now();
+        let unready = Vec::new();
+        let timer = Timer::default();
+        let mut inotify = Self {
+            device,
+            buffer,
+            watches,
+            removed,
+            read_at,
+            unready,
+            timer,
+        };
+
+        inotify.watch(path)?;
+
+        Ok(inotify)
+    }
+
+    /// Start watching a directory
+    fn watch(&mut self, path: &Path) -> io::Result<()> {
+        let listen = self.device.as_raw_fd();
+        let wd =
+            inotify_watch(listen, path, IN_ATTRIB | IN_CREATE | IN_DELETE)?;
+        self.watches.retain(|(w, _)| *w != wd);
+        self.watches.push((wd, path.to_path_buf()));
+
+        Ok(())
+    }
+
+    /// Get the next path created or changed (and whether or not it's a
+    /// directory) from the buffer of events
+    fn event(&mut self) -> Option<(PathBuf, bool)> {
+        while !self.buffer.is_empty() {
+            let begin: [u8; size_of::<InotifyEv>()] =
+                self.buffer[..size_of::<InotifyEv>()].try_into().unwrap();
+            let inotify_ev: InotifyEv = unsafe { mem::transmute(begin) };
+            let len = inotify_ev.len.try_into().unwrap_or(usize::MAX);
+            let bytes = &self.buffer[size_of::<InotifyEv>()..][..len];
+            let bytes = bytes.split(|n| *n == b'\0').next().unwrap_or_default();
+            // Names are kept as bytes, since they don't have to be UTF-8
+            let filename = OsStr::from_bytes(bytes).to_os_string();
+            let dir = self
+                .watches
+                .iter()
+                .find(|(wd, _)| *wd == inotify_ev.wd)
+                .map(|(_, dir)| dir.clone());
+
+            self.buffer.drain(..size_of::<InotifyEv>() + len);
+
+            if inotify_ev.mask & IN_IGNORED != 0 {
+                self.watches.retain(|(wd, _)| *wd != inotify_ev.wd);
+                continue;
             }
-            let mut len = MaybeUninit::uninit();
-            let ret = unsafe {
-                ioctl(searcher.device.as_raw_fd(), FIONREAD, len.as_mut_ptr())
+
+            // Events without a name are for the watched directory itself
+            let Some(dir) = dir.filter(|_| !filename.is_empty()) else {
+                continue;
             };
-            assert!(ret >= 0);
-            let len = unsafe { len.assume_init() };
+            let path = dir.join(filename);
+            let is_dir = inotify_ev.mask & IN_ISDIR != 0;
 
-            searcher
-                .buffer
-                .resize(len.try_into().unwrap_or(usize::MAX), 0);
+            // Removed subdirectories have their watch removed automatically
+            if inotify_ev.mask & IN_DELETE != 0 {
+                if !is_dir {
+                    self.unready.retain(|(_, unready)| *unready != path);
+                    self.removed.push_back(path);
+                }
 
-            if let Err(e) = searcher.device.read_exact(&mut searcher.buffer) {
-                dbg!(e);
+                continue;
             }
 
-            if let Some(found) = searcher.find() {
-                return Ready(found);
+            if is_dir {
+                return Some((path, is_dir));
+            }
+
+            // Device files are often made accessible by udev right after
+            // they're created, so wait for their permissions to change
+            if inotify_ev.mask & IN_CREATE != 0 && !is_accessible(&path) {
+                self.unready.push((self.read_at, path));
+                continue;
+            }
+
+            if let Some(index) = self
+                .unready
+                .iter()
+                .position(|(_, unready)| *unready == path)
+            {
+                if !is_accessible(&path) {
+                    continue;
+                }
+
+                self.unready.remove(index);
+            }
+
+            return Some((path, is_dir));
+        }
+
+        None
+    }
+
+    fn poll_event(&mut self, task: &mut Task<'_>) -> Poll<(PathBuf, bool)> {
+        loop {
+            if let Some(event) = self.event() {
+                return Ready(event);
+            }
+
+            // Read events until one is ready, then wait for more
+            if self.read() {
+                continue;
+            }
+
+            if let Some(path) = self.expired() {
+                return Ready((path, false));
+            }
+
+            if Pin::new(&mut self.device).poll_next(task).is_ready() {
+                continue;
             }
+
+            // Also wait to give up on the oldest unready file
+            let Some(&(created, _)) = self.unready.first() else {
+                self.timer.clear();
+                return Pending;
+            };
+
+            self.timer.set(created + UNREADY);
+
+            if Pin::new(&mut self.timer).poll_next(task).is_pending() {
+                return Pending;
+            }
+        }
+    }
+
+    /// Take the oldest unready file if it's been waited on for too long, so
+    /// it's found anyway (with the time it was created)
+    fn expired(&mut self) -> Option<PathBuf> {
+        let &(created, _) = self.unready.first()?;
+
+        if Instant::now() < created + UNREADY {
+            return None;
+        }
+
+        let (created, path) = self.unready.remove(0);
+
+        self.read_at = created;
+
+        Some(path)
+    }
+
+    /// Read the events that are ready into the buffer, without waiting,
+    /// returning whether there were any
+    fn read(&mut self) -> bool {
+        // https://github.com/torvalds/linux/blob/dbad9ce9397ef7f891b4ff44bad694add673c1a1/include/uapi/asm-generic/ioctls.h#L46
+        const FIONREAD: c_ulong = 0x541B;
+        extern "C" {
+            fn ioctl(fd: RawFd, req: c_ulong, arg: *mut c_void) -> c_int;
+        }
+        let mut len = MaybeUninit::<c_uint>::uninit();
+        let ptr = len.as_mut_ptr().cast();
+        let ret = unsafe { ioctl(self.device.as_raw_fd(), FIONREAD, ptr) };
+        assert!(ret >= 0);
+        let len = unsafe { len.assume_init() };
+
+        if len == 0 {
+            return false;
+        }
+
+        self.buffer.resize(len.try_into().unwrap_or(usize::MAX), 0);
+        self.read_at = Instant::now();
+
+        // Events can't be parsed out of a partial read, so they're dropped
+        if self.device.read_exact(&mut self.buffer).is_err() {
+            self.buffer.clear();
+            return false;
         }
 
-        Pending
+        true
     }
 }
@@ -0,0 +1,62 @@
+use std::{
+    fs::File,
+    io::{self, Read},
+    os::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd, RawFd},
+};
+
+use pasts::prelude::*;
+use smelling_salts::{Device, Watch};
+
+/// A non-blocking file descriptor that events are read from
+#[derive(Debug)]
+pub(super) enum Listener {
+    /// Wakes the task from smelling_salts' reactor thread
+    Watched(Device),
+    /// Left for another reactor to watch, so it never wakes the task
+    Unwatched(File),
+}
+
+impl Listener {
+    pub(super) fn new(fd: OwnedFd, watched: bool) -> Self {
+        if watched {
+            Self::Watched(Device::new(fd, Watch::INPUT))
+        } else {
+            Self::Unwatched(fd.into())
+        }
+    }
+}
+
+impl AsFd for Listener {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        match self {
+            Self::Watched(device) => device.as_fd(),
+            Self::Unwatched(file) => file.as_fd(),
+        }
+    }
+}
+
+impl AsRawFd for Listener {
+    fn as_raw_fd(&self) -> RawFd {
+        self.as_fd().as_raw_fd()
+    }
+}
+
+impl Read for Listener {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Watched(device) => device.read(buf),
+            Self::Unwatched(file) => file.read(buf),
+        }
+    }
+}
+
+impl Notify for Listener {
+    type Event = ();
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll {
+        match self.get_mut() {
+            Self::Watched(device) => Pin::new(device).poll_next(task),
+            Self::Unwatched(_) => Pending,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::{collections::VecDeque, mem, task::Waker};
+
+use pasts::prelude::*;
+
+/// Queue of items sent to tasks, and the tasks waiting for them
+#[derive(Debug)]
+pub(crate) struct Mailbox<T> {
+    pub(crate) queue: VecDeque<T>,
+    wakers: Vec<Waker>,
+}
+
+impl<T> Default for Mailbox<T> {
+    fn default() -> Self {
+        let queue = VecDeque::new();
+        let wakers = Vec::new();
+
+        Self { queue, wakers }
+    }
+}
+
+impl<T> Mailbox<T> {
+    /// Queue an item, taking the wakers of the tasks waiting for one
+    ///
+    /// They're woken by the caller, once the mailbox isn't locked.
+    pub(crate) fn send(&mut self, item: T) -> Vec<Waker> {
+        self.queue.push_back(item);
+        self.take_wakers()
+    }
+
+    /// Take the wakers of the tasks waiting
+    pub(crate) fn take_wakers(&mut self) -> Vec<Waker> {
+        mem::take(&mut self.wakers)
+    }
+
+    /// Wake a task once something is sent
+    pub(crate) fn wait(&mut self, waker: &Waker) {
+        if !self.wakers.iter().any(|w| w.will_wake(waker)) {
+            self.wakers.push(waker.clone());
+        }
+    }
+
+    /// Take the next item, or wait for one
+    pub(crate) fn poll_recv(&mut self, task: &mut Task<'_>) -> Poll<T> {
+        if let Some(item) = self.queue.pop_front() {
+            return Ready(item);
+        }
+
+        self.wait(task.waker());
+
+        Pending
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::Instant;
+
+use pasts::prelude::*;
+
+use crate::{blocking, Found, Searcher};
+
+/// Lookit [`Notify`].  Lets you know when a device is [`Found`] by any of
+/// multiple searchers.
+///
+/// Use [`Found::kind()`] to tell which kind of searcher found a device.
+/// Devices found by different searchers at once are delivered in order of
+/// their [`Found::timestamp()`].
+#[derive(Debug)]
+pub struct Merged {
+    searchers: Vec<Searcher>,
+    /// Next device found by each searcher, not yet delivered
+    ready: Vec<Option<Found>>,
+    /// Index of the searcher to check first, so none are starved
+    next: usize,
+}
+
+impl Merged {
+    pub(crate) fn new(searchers: Vec<Searcher>) -> Self {
+        let ready = searchers.iter().map(|_| None).collect();
+        let next = 0;
+
+        Self {
+            searchers,
+            ready,
+            next,
+        }
+    }
+
+    /// Get the searchers being merged.
+    pub fn searchers(&self) -> &[Searcher] {
+        &self.searchers
+    }
+
+    /// Get the next device found by any of the searchers, or `None` if there
+    /// isn't one yet, without waiting (see [`Searcher::try_next()`]).
+    ///
+    /// Watch the file descriptors of all the [searchers](Merged::searchers)
+    /// to know when to call this again.
+    pub fn try_next(&mut self) -> Option<Found> {
+        blocking::try_poll(self)
+    }
+}
+
+impl Notify for Merged {
+    type Event = Found;
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Found> {
+        let this = self.get_mut();
+        let len = this.searchers.len();
+        let mut earliest: Option<(usize, Instant)> = None;
+
+        for offset in 0..len {
+            let index = (this.next + offset) % len;
+            let ready = &mut this.ready[index];
+
+            if ready.is_none() {
+                let searcher = &mut this.searchers[index];
+
+                if let Ready(found) = Pin::new(searcher).poll_next(task) {
+                    *ready = Some(found);
+                }
+            }
+
+            let Some(timestamp) = ready.as_ref().map(Found::timestamp) else {
+                continue;
+            };
+
+            // Ties go to the searcher checked first
+            if earliest.map_or(true, |(_, earliest)| timestamp < earliest) {
+                earliest = Some((index, timestamp));
+            }
+        }
+
+        let Some((index, _)) = earliest else {
+            return Pending;
+        };
+
+        this.next = (index + 1) % len;
+
+        this.ready[index].take().map_or(Pending, Ready)
+    }
+}
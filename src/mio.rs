@@ -0,0 +1,134 @@
+use std::{io, os::fd::AsRawFd};
+
+use mio::{event::Source, unix::SourceFd, Interest, Registry, Token};
+
+use crate::{Found, Searcher};
+
+/// Lookit searcher for mio's event loop, registered as a [`Source`].
+///
+/// Created with
+/// [`SearcherBuilder::build_mio()`](crate::SearcherBuilder::build_mio), so
+/// it's not watched by smelling_salts.  Once it's readable, call
+/// [`MioSearcher::try_next()`] until it returns `None` before polling again.
+///
+/// Devices that are found after a delay (with
+/// [`debounce()`](Searcher::debounce), or when waiting for a device file's
+/// permissions to change) don't make it readable, so also call `try_next()`
+/// again after the delay, with a timeout on the poll.
+#[derive(Debug)]
+pub struct MioSearcher(Searcher);
+
+impl MioSearcher {
+    pub(crate) fn new(searcher: Searcher) -> Self {
+        Self(searcher)
+    }
+
+    /// Get the next device found, or `None` if there isn't one yet, without
+    /// waiting.
+    pub fn try_next(&mut self) -> Option<Found> {
+        self.0.try_next()
+    }
+
+    /// Get the searcher back.
+    ///
+    /// Deregister it first, since it's still registered with the file
+    /// descriptor it's searching with.
+    pub fn into_inner(self) -> Searcher {
+        self.0
+    }
+
+    /// Run `f` with the file descriptor, failing if the backend doesn't have
+    /// one (like [`Backend::Scan`](crate::Backend::Scan))
+    fn with_fd(
+        &self,
+        f: impl FnOnce(&mut SourceFd<'_>) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let fd = self.0.as_fd().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                "backend doesn't have a file descriptor",
+            )
+        })?;
+
+        f(&mut SourceFd(&fd.as_raw_fd()))
+    }
+}
+
+impl Source for MioSearcher {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.with_fd(|fd| fd.register(registry, token, interests))
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.with_fd(|fd| fd.reregister(registry, token, interests))
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.with_fd(|fd| fd.deregister(registry))
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::{fs, path::PathBuf, thread, time::Duration};
+
+    use mio::{Events, Interest, Poll, Token};
+
+    use crate::{Backend, SearcherBuilder};
+
+    #[test]
+    fn registers_fd() {
+        let dir = std::env::temp_dir()
+            .join(format!("lookit-mio-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("dev0"), "").unwrap();
+
+        let path = format!("{}/", dir.display());
+        let mut searcher = SearcherBuilder::with_path(path, ["dev"])
+            .backends(&[Backend::Inotify])
+            .build_mio();
+        let mut poll = Poll::new().unwrap();
+        let mut events = Events::with_capacity(4);
+        let mut found = Vec::<PathBuf>::new();
+
+        poll.registry()
+            .register(&mut searcher, Token(0), Interest::READABLE)
+            .unwrap();
+
+        let adding = dir.join("dev1");
+        let adder = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            fs::write(adding, "").unwrap();
+        });
+
+        loop {
+            while let Some(device) = searcher.try_next() {
+                found.push(device.path().into());
+            }
+
+            if found.len() == 2 {
+                break;
+            }
+
+            poll.poll(&mut events, Some(Duration::from_secs(5)))
+                .unwrap();
+            assert!(!events.is_empty(), "never readable");
+        }
+
+        poll.registry().deregister(&mut searcher).unwrap();
+        adder.join().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(found, [dir.join("dev0"), dir.join("dev1")]);
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex, Weak},
+};
+
+use async_main::Spawn;
+use pasts::prelude::*;
+
+use crate::{mailbox::Mailbox, Found, Searcher};
+
+type Shared<T> = Arc<Mutex<Mailbox<T>>>;
+
+/// A searcher running on the monitor task, and where it sends found devices
+type Subscriber = (Searcher, Shared<Found>);
+
+fn send<T>(shared: &Shared<T>, item: T) {
+    let wakers = shared.lock().unwrap().send(item);
+
+    for waker in wakers {
+        waker.wake();
+    }
+}
+
+fn wake<T>(shared: &Mutex<Mailbox<T>>) {
+    let wakers = shared.lock().unwrap().take_wakers();
+
+    for waker in wakers {
+        waker.wake();
+    }
+}
+
+/// Spawn the shared monitor as a background task on `spawner`.
+///
+/// Searchers handed to the returned [`Monitor`] are polled on that task, and
+/// found devices are delivered to their [`Subscription`]s.  The task finishes
+/// once every handle and subscription has been dropped.
+///
+/// ```rust, no_run
+/// use lookit::Searcher;
+/// use pasts::prelude::*;
+///
+/// #[async_main::async_main]
+/// async fn main(spawner: impl async_main::Spawn) {
+///     let monitor = lookit::spawn_monitor(&spawner);
+///     let mut cameras = monitor.subscribe(Searcher::with_camera());
+///
+///     loop {
+///         let file = cameras.next().await;
+///         dbg!(file);
+///     }
+/// }
+/// ```
+pub fn spawn_monitor(spawner: &impl Spawn) -> Monitor {
+    let requests = Shared::default();
+    let subscribers = Vec::new();
+
+    spawner.spawn(Background {
+        requests: requests.clone(),
+        subscribers,
+    });
+
+    Monitor { requests }
+}
+
+/// Handle to the monitor task started with [`spawn_monitor()`].
+#[derive(Clone)]
+pub struct Monitor {
+    requests: Shared<Subscriber>,
+}
+
+impl fmt::Debug for Monitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Monitor").finish_non_exhaustive()
+    }
+}
+
+impl Monitor {
+    /// Run a searcher on the monitor task, subscribing to the devices it
+    /// finds.
+    pub fn subscribe(&self, searcher: Searcher) -> Subscription {
+        let found = Shared::default();
+        let requests = Arc::downgrade(&self.requests);
+
+        send(&self.requests, (searcher, found.clone()));
+
+        Subscription { found, requests }
+    }
+}
+
+impl Drop for Monitor {
+    fn drop(&mut self) {
+        // Let the task check if it should finish
+        wake(&self.requests);
+    }
+}
+
+/// Lookit [`Notify`].  Receives devices [`Found`] by a searcher running on the
+/// monitor task.
+pub struct Subscription {
+    found: Shared<Found>,
+    requests: Weak<Mutex<Mailbox<Subscriber>>>,
+}
+
+impl fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription").finish_non_exhaustive()
+    }
+}
+
+impl Notify for Subscription {
+    type Event = Found;
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Found> {
+        self.found.lock().unwrap().poll_recv(task)
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        // Let the task stop the searcher
+        if let Some(requests) = self.requests.upgrade() {
+            wake(&requests);
+        }
+    }
+}
+
+/// The monitor task
+struct Background {
+    requests: Shared<Subscriber>,
+    subscribers: Vec<Subscriber>,
+}
+
+impl Future for Background {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<()> {
+        let this = self.get_mut();
+
+        {
+            let mut requests = this.requests.lock().unwrap();
+
+            this.subscribers.extend(requests.queue.drain(..));
+            requests.wait(task.waker());
+        }
+
+        // Stop searchers nobody is subscribed to anymore
+        this.subscribers
+            .retain(|(_, found)| Arc::strong_count(found) > 1);
+
+        if this.subscribers.is_empty() && Arc::strong_count(&this.requests) == 1
+        {
+            return Ready(());
+        }
+
+        for (searcher, found) in this.subscribers.iter_mut() {
+            while let Ready(device) = Pin::new(&mut *searcher).poll_next(task) {
+                send(found, device);
+            }
+        }
+
+        Pending
+    }
+}
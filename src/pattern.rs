@@ -0,0 +1,139 @@
+/// Check if `text` matches a glob `pattern`
+///
+/// Supports `*` (any run of characters), `?` (any one character), character
+/// classes (like `[0-9]` or `[!0-9]`), and alternatives separated by `|`.
+pub(crate) fn matches(pattern: &str, text: &str) -> bool {
+    let text: Vec<char> = text.chars().collect();
+
+    pattern.split('|').any(|alternative| {
+        let alternative: Vec<char> = alternative.chars().collect();
+
+        glob(&alternative, &text)
+    })
+}
+
+/// Match one character against a class (the pattern after `[`), returning the
+/// rest of the pattern after `]` if it matches
+fn class(pattern: &[char], c: char) -> Option<Option<&[char]>> {
+    let (negated, mut rest) = match pattern {
+        ['!' | '^', rest @ ..] => (true, rest),
+        rest => (false, rest),
+    };
+    let mut matched = false;
+    let mut first = true;
+
+    loop {
+        match rest {
+            // Unterminated classes aren't classes
+            [] => return None,
+            [']', after @ ..] if !first => {
+                return Some((matched != negated).then_some(after));
+            }
+            [start, '-', end, after @ ..] if *end != ']' => {
+                matched |= (*start..=*end).contains(&c);
+                rest = after;
+            }
+            [one, after @ ..] => {
+                matched |= *one == c;
+                rest = after;
+            }
+        }
+
+        first = false;
+    }
+}
+
+/// Match text against a pattern without alternatives
+fn glob(mut pattern: &[char], mut text: &[char]) -> bool {
+    // Where to resume after the last `*`, if matching fails
+    let mut backtrack = None;
+
+    loop {
+        let step = match (pattern, text) {
+            ([], []) => return true,
+            (['*', rest @ ..], _) => {
+                backtrack = Some((rest, text));
+                pattern = rest;
+                continue;
+            }
+            (['?', rest @ ..], [_, after @ ..]) => Some((rest, after)),
+            (['[', rest @ ..], [c, after @ ..]) => match class(rest, *c) {
+                Some(rest) => rest.map(|rest| (rest, after)),
+                None => (*c == '[').then_some((rest, after)),
+            },
+            ([p, rest @ ..], [c, after @ ..]) => {
+                (p == c).then_some((rest, after))
+            }
+            _ => None,
+        };
+
+        if let Some((rest, after)) = step {
+            pattern = rest;
+            text = after;
+            continue;
+        }
+
+        // Let the last `*` match one more character, and try again
+        let Some((rest, [_, after @ ..])) = backtrack else {
+            return false;
+        };
+
+        backtrack = Some((rest, after));
+        pattern = rest;
+        text = after;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn star_backtracks() {
+        assert!(matches("a*b*c", "aXbYbc"));
+        assert!(matches("a*b*c", "abc"));
+        assert!(!matches("a*b*c", "aXbYbcd"));
+        assert!(matches("*0", "event10"));
+    }
+
+    #[test]
+    fn empty_text() {
+        assert!(matches("", ""));
+        assert!(matches("*", ""));
+        assert!(!matches("?", ""));
+        assert!(!matches("[a]", ""));
+        assert!(!matches("a", ""));
+    }
+
+    #[test]
+    fn negated_class() {
+        assert!(matches("[!a-c]", "d"));
+        assert!(!matches("[!a-c]", "b"));
+        assert!(matches("[^a-c]", "d"));
+        assert!(!matches("[!a-c]", ""));
+    }
+
+    #[test]
+    fn bracket_first_in_class() {
+        assert!(matches("[]a]", "]"));
+        assert!(matches("[]a]", "a"));
+        assert!(!matches("[]a]", "b"));
+        assert!(matches("[!]]", "a"));
+        assert!(!matches("[!]]", "]"));
+    }
+
+    #[test]
+    fn unterminated_class() {
+        assert!(matches("[ab", "[ab"));
+        assert!(!matches("[ab", "a"));
+        assert!(matches("js[", "js["));
+    }
+
+    #[test]
+    fn empty_alternative() {
+        assert!(matches("event*|", ""));
+        assert!(matches("event*|", "event3"));
+        assert!(!matches("event*|", "mouse0"));
+        assert!(matches("|js*", "js0"));
+    }
+}
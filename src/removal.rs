@@ -0,0 +1,97 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use pasts::prelude::*;
+
+use crate::{timer::Timer, Interface, Platform};
+
+/// How often to check when changes can't be watched for (or are missed)
+const PROBE: Duration = Duration::from_secs(1);
+
+/// Watch for a device to be unplugged, given its platform path.
+///
+/// This works on devices that are already connected, so a worker can stop
+/// before reads start failing.  For a device that was just found, use
+/// [`Found::removal()`](crate::Found::removal) before connecting.  Display
+/// connectors stay in place when unplugged, so their removal isn't noticed.
+///
+/// ```rust, no_run
+/// use lookit::Searcher;
+/// use pasts::prelude::*;
+///
+/// #[async_main::async_main]
+/// async fn main(_spawner: impl async_main::Spawn) {
+///     let found = Searcher::with_camera().next().await;
+///     let mut removal = found.removal();
+///     let Ok((device, _access)) = found.connect() else {
+///         return;
+///     };
+///
+///     removal.next().await;
+///     println!("Camera was unplugged");
+///     drop(device);
+/// }
+/// ```
+pub fn watch_removal(path: impl AsRef<Path>) -> Removal {
+    let path = path.as_ref().to_path_buf();
+    let closed = Platform::watch_removal(&path);
+    let timer = Timer::default();
+
+    Removal {
+        path,
+        closed,
+        timer,
+    }
+}
+
+/// Lookit [`Notify`].  Lets you know when a device was removed, from
+/// [`watch_removal()`].
+pub struct Removal {
+    path: PathBuf,
+    closed: Option<<Platform as Interface>::Closed>,
+    timer: Timer,
+}
+
+impl fmt::Debug for Removal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Removal")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Notify for Removal {
+    type Event = ();
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll {
+        let this = self.get_mut();
+
+        loop {
+            if !this.path.exists() {
+                return Ready(());
+            }
+
+            let mut changed = false;
+
+            if let Some(ref mut closed) = this.closed {
+                changed = Pin::new(closed).poll_next(task).is_ready();
+            }
+
+            if !this.timer.is_set() {
+                this.timer.set(Instant::now() + PROBE);
+            }
+
+            if Pin::new(&mut this.timer).poll_next(task).is_ready() {
+                this.timer.set(Instant::now() + PROBE);
+                changed = true;
+            }
+
+            if !changed {
+                return Pending;
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+#[cfg(target_os = "linux")]
+use std::io;
+use std::{fmt, task::Waker};
+
+use pasts::prelude::*;
+
+use crate::Device;
+
+/// Error reading from an input device after it's been revoked
+/// (`EVIOCREVOKE`)
+#[cfg(target_os = "linux")]
+const ENODEV: i32 = 19;
+
+/// Event from a [`Revocable`] device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Revocation {
+    /// The device may have input to read
+    Readable,
+    /// Access to the device was revoked (like when the compositor or seat
+    /// manager switched to another VT), and it should no longer be read from
+    /// until it's [regranted](Revocable::regrant)
+    Revoked,
+    /// Access to the device was [regranted](Revocable::regrant), and it can
+    /// be read from again
+    Regranted,
+}
+
+/// Lookit [`Notify`].  Wraps a connected input device whose access can be
+/// revoked with `EVIOCREVOKE` (for example, one opened through logind or
+/// seatd), so revocation is an event rather than a read error.
+pub struct Revocable {
+    device: Option<Device>,
+    /// Revoked or regranted event, not yet returned
+    event: Option<Revocation>,
+    waker: Option<Waker>,
+}
+
+impl fmt::Debug for Revocable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Revocable")
+            .field("revoked", &self.is_revoked())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Revocable {
+    /// Wrap a connected input device.
+    pub fn new(device: Device) -> Self {
+        let device = Some(device);
+        let event = None;
+        let waker = None;
+
+        Self {
+            device,
+            event,
+            waker,
+        }
+    }
+
+    /// Check if access to the device has been revoked (and not regranted).
+    pub fn is_revoked(&self) -> bool {
+        self.device.is_none()
+    }
+
+    /// Get the device, unless access to it has been revoked.
+    pub fn device(&mut self) -> Option<&mut Device> {
+        self.device.as_mut()
+    }
+
+    /// Mark the device as revoked, closing it.
+    ///
+    /// This happens automatically when reading fails because the device was
+    /// revoked, but can be called early when the seat manager announces it
+    /// (like logind's `PauseDevice` signal).
+    pub fn revoke(&mut self) {
+        if self.device.take().is_some() {
+            self.event = Some(Revocation::Revoked);
+            self.wake();
+        }
+    }
+
+    /// Replace the device with one access has been granted to again (like the
+    /// one sent with logind's `ResumeDevice` signal).
+    pub fn regrant(&mut self, device: Device) {
+        self.device = Some(device);
+        self.event = Some(Revocation::Regranted);
+        self.wake();
+    }
+
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl io::Read for Revocable {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(ref mut device) = self.device else {
+            return Err(io::ErrorKind::NotConnected.into());
+        };
+        let result = device.read(buf);
+
+        if let Err(ref e) = result {
+            if e.raw_os_error() == Some(ENODEV) {
+                self.revoke();
+            }
+        }
+
+        result
+    }
+}
+
+impl Notify for Revocable {
+    type Event = Revocation;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        task: &mut Task<'_>,
+    ) -> Poll<Revocation> {
+        let this = self.get_mut();
+
+        if let Some(event) = this.event.take() {
+            return Ready(event);
+        }
+
+        let Some(ref mut device) = this.device else {
+            // Wait to be regranted
+            this.waker = Some(task.waker().clone());
+            return Pending;
+        };
+
+        Pin::new(device)
+            .poll_next(task)
+            .map(|()| Revocation::Readable)
+    }
+}
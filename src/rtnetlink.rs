@@ -0,0 +1,468 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    ffi::{OsStr, OsString},
+    fs,
+    io::{self, Read},
+    mem::size_of,
+    os::{
+        fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+        raw::{c_int, c_void},
+        unix::ffi::OsStrExt,
+    },
+    path::{Path, PathBuf},
+};
+
+use pasts::prelude::*;
+
+use super::listener::Listener;
+
+// https://github.com/torvalds/linux/blob/dbad9ce9397ef7f891b4ff44bad694add673c1a1/include/uapi/linux/netlink.h
+
+const AF_NETLINK: c_int = 16;
+const SOCK_DGRAM: c_int = 2;
+const SOCK_NONBLOCK: c_int = 0o4000;
+const SOCK_CLOEXEC: c_int = 0o2000000;
+const NETLINK_ROUTE: c_int = 0;
+
+// https://github.com/torvalds/linux/blob/dbad9ce9397ef7f891b4ff44bad694add673c1a1/include/uapi/linux/rtnetlink.h
+
+/// Multicast group for network interface changes
+const RTMGRP_LINK: u32 = 1;
+const RTM_NEWLINK: u16 = 16;
+const RTM_DELLINK: u16 = 17;
+
+// https://github.com/torvalds/linux/blob/dbad9ce9397ef7f891b4ff44bad694add673c1a1/include/uapi/linux/if_link.h
+
+const IFLA_IFNAME: u16 = 3;
+
+/// Size of struct nlmsghdr
+const NLMSGHDR_LEN: usize = 16;
+/// Size of struct ifinfomsg
+const IFINFOMSG_LEN: usize = 16;
+/// Size of struct rtattr
+const RTATTR_LEN: usize = 4;
+
+/// Directory network interfaces are found in
+const CLASS: &str = "/sys/class/net/";
+
+/// Get the indices and names of the network interfaces that are there now
+fn interfaces() -> HashMap<i32, OsString> {
+    fs::read_dir(CLASS)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let index =
+                fs::read_to_string(entry.path().join("ifindex")).ok()?;
+
+            Some((index.trim().parse().ok()?, entry.file_name()))
+        })
+        .collect()
+}
+
+/// struct sockaddr_nl, from C.
+#[repr(C)]
+struct SockaddrNl {
+    nl_family: u16,
+    nl_pad: u16,
+    nl_pid: u32,
+    nl_groups: u32,
+}
+
+extern "C" {
+    fn socket(domain: c_int, kind: c_int, protocol: c_int) -> RawFd;
+    fn bind(fd: RawFd, addr: *const c_void, len: u32) -> c_int;
+}
+
+/// Round up to the 4 byte alignment of netlink messages and attributes
+fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// Read a native-endian `u16` from the start of a buffer
+fn read_u16(bytes: &[u8]) -> Option<u16> {
+    Some(u16::from_ne_bytes(bytes.get(..2)?.try_into().ok()?))
+}
+
+/// Read a native-endian `u32` from the start of a buffer
+fn read_u32(bytes: &[u8]) -> Option<u32> {
+    Some(u32::from_ne_bytes(bytes.get(..4)?.try_into().ok()?))
+}
+
+/// Listens for network interfaces being added over rtnetlink
+#[derive(Debug)]
+pub(super) struct Rtnetlink {
+    device: Listener,
+    buffer: Vec<u8>,
+    /// Names of interfaces that are already there, by index (which stays the
+    /// same when an interface is renamed)
+    known: HashMap<i32, OsString>,
+    /// Paths of interfaces added, not yet returned
+    queue: VecDeque<PathBuf>,
+    /// Paths of interfaces removed, not yet taken
+    removed: VecDeque<PathBuf>,
+}
+
+impl Rtnetlink {
+    pub(super) fn new(watched: bool) -> io::Result<Self> {
+        let fd = unsafe {
+            socket(
+                AF_NETLINK,
+                SOCK_DGRAM | SOCK_NONBLOCK | SOCK_CLOEXEC,
+                NETLINK_ROUTE,
+            )
+        };
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        let addr = SockaddrNl {
+            nl_family: AF_NETLINK as u16,
+            nl_pad: 0,
+            nl_pid: 0,
+            nl_groups: RTMGRP_LINK,
+        };
+        let len = size_of::<SockaddrNl>() as u32;
+        let ptr: *const SockaddrNl = &addr;
+
+        if unsafe { bind(fd.as_raw_fd(), ptr.cast(), len) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // Changes to interfaces already there aren't additions
+        Ok(Self::with_fd(fd, watched, interfaces()))
+    }
+
+    /// Listen on an already bound socket, for interfaces other than the ones
+    /// already known
+    fn with_fd(
+        fd: OwnedFd,
+        watched: bool,
+        known: HashMap<i32, OsString>,
+    ) -> Self {
+        let device = Listener::new(fd, watched);
+        let buffer = vec![0; 8192];
+        let queue = VecDeque::new();
+        let removed = VecDeque::new();
+
+        Self {
+            device,
+            buffer,
+            known,
+            queue,
+            removed,
+        }
+    }
+
+    /// Listen on a socket bound before an `exec()`, with the indices and
+    /// paths of the interfaces known then
+    ///
+    /// Interfaces added, removed or renamed since then are found, even if
+    /// their messages were lost while the socket wasn't being read.
+    pub(super) fn restore(fd: OwnedFd, known: &[(i32, PathBuf)]) -> Self {
+        let known = known
+            .iter()
+            .filter_map(|(index, path)| {
+                Some((*index, path.strip_prefix(CLASS).ok()?.into()))
+            })
+            .collect();
+        let mut rtnetlink = Self::with_fd(fd, true, known);
+        let mut interfaces: Vec<_> = interfaces().into_iter().collect();
+        let mut removed: Vec<_> = rtnetlink
+            .known
+            .keys()
+            .filter(|index| !interfaces.iter().any(|(i, _)| i == *index))
+            .copied()
+            .collect();
+
+        removed.sort();
+        interfaces.sort();
+
+        for index in removed {
+            rtnetlink.forget(index);
+        }
+
+        for (index, name) in interfaces {
+            rtnetlink.update(index, name);
+        }
+
+        rtnetlink
+    }
+
+    /// Get the socket
+    pub(super) fn device(&self) -> &Listener {
+        &self.device
+    }
+
+    /// Get the socket, paths not yet returned, and the indices and paths of
+    /// the interfaces known
+    pub(super) fn into_parts(
+        self,
+    ) -> (
+        Listener,
+        VecDeque<PathBuf>,
+        impl Iterator<Item = (i32, PathBuf)>,
+    ) {
+        let known = self
+            .known
+            .into_iter()
+            .map(|(index, name)| (index, Path::new(CLASS).join(name)));
+
+        (self.device, self.queue, known)
+    }
+
+    /// Record the name of an interface, queueing it if it's new or renamed
+    fn update(&mut self, index: i32, name: OsString) {
+        match self.known.insert(index, name.clone()) {
+            Some(old) if old == name => return,
+            // Renamed interfaces (like by udev) are gone under their old name
+            Some(old) => self.remove(old),
+            None => {}
+        }
+
+        self.queue.push_back(Path::new(CLASS).join(name));
+    }
+
+    /// Forget an interface, if it was known
+    fn forget(&mut self, index: i32) {
+        if let Some(name) = self.known.remove(&index) {
+            self.remove(name);
+        }
+    }
+
+    /// Queue an interface's path as removed, and drop it from the paths not
+    /// yet returned
+    fn remove(&mut self, name: OsString) {
+        let path = Path::new(CLASS).join(name);
+
+        self.queue.retain(|queued| *queued != path);
+        self.removed.push_back(path);
+    }
+
+    /// Parse the index of the interface out of an `ifinfomsg`
+    fn index(payload: &[u8]) -> Option<i32> {
+        // After the family, padding and device type
+        let index = payload.get(4..8)?;
+
+        Some(i32::from_ne_bytes(index.try_into().ok()?))
+    }
+
+    /// Take the paths of interfaces removed since last time
+    pub(super) fn take_removed(&mut self) -> VecDeque<PathBuf> {
+        std::mem::take(&mut self.removed)
+    }
+
+    /// Parse the name of the interface out of an `ifinfomsg` and its
+    /// attributes
+    fn name(mut payload: &[u8]) -> Option<OsString> {
+        payload = payload.get(IFINFOMSG_LEN..)?;
+
+        while payload.len() >= RTATTR_LEN {
+            let len = usize::from(read_u16(payload)?);
+            let kind = read_u16(&payload[2..])?;
+            let data = payload.get(RTATTR_LEN..len)?;
+
+            if kind == IFLA_IFNAME {
+                let name = data.split(|n| *n == b'\0').next()?;
+
+                // Names are kept as bytes, since they don't have to be UTF-8
+                return Some(OsStr::from_bytes(name).to_os_string());
+            }
+
+            payload = payload.get(align(len).max(RTATTR_LEN)..)?;
+        }
+
+        None
+    }
+
+    /// Handle the link messages in a datagram
+    fn handle(&mut self, mut messages: &[u8]) {
+        while messages.len() >= NLMSGHDR_LEN {
+            let Some(len) = read_u32(messages) else { break };
+            let len = usize::try_from(len).unwrap_or(usize::MAX);
+            let Some(kind) = read_u16(&messages[4..]) else {
+                break;
+            };
+            let Some(payload) = messages.get(NLMSGHDR_LEN..len) else {
+                break;
+            };
+
+            let index = Self::index(payload);
+
+            if let (RTM_NEWLINK, Some(index)) = (kind, index) {
+                if let Some(name) = Self::name(payload) {
+                    self.update(index, name);
+                }
+            } else if let (RTM_DELLINK, Some(index)) = (kind, index) {
+                self.forget(index);
+            }
+
+            let Some(rest) = messages.get(align(len).max(NLMSGHDR_LEN)..)
+            else {
+                break;
+            };
+
+            messages = rest;
+        }
+    }
+
+    /// Get the path of the next network interface added
+    pub(super) fn poll_added(&mut self, task: &mut Task<'_>) -> Poll<PathBuf> {
+        loop {
+            if let Some(path) = self.queue.pop_front() {
+                return Ready(path);
+            }
+
+            // Read messages until one is ready, then wait for more
+            if let Ok(len) = self.device.read(&mut self.buffer) {
+                let buffer = std::mem::take(&mut self.buffer);
+
+                self.handle(&buffer[..len]);
+                self.buffer = buffer;
+                continue;
+            }
+
+            if Pin::new(&mut self.device).poll_next(task).is_pending() {
+                return Pending;
+            }
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::fs::File;
+
+    use super::*;
+
+    /// Build a `struct rtattr` with its data, padded to its alignment
+    fn attr(kind: u16, data: &[u8]) -> Vec<u8> {
+        let len = (RTATTR_LEN + data.len()) as u16;
+        let mut attr = [len.to_ne_bytes(), kind.to_ne_bytes()].concat();
+
+        attr.extend(data);
+        attr.resize(align(attr.len()), 0);
+        attr
+    }
+
+    /// Build a `struct ifinfomsg` for an interface, followed by attributes
+    fn ifinfomsg(index: i32, attrs: &[&[u8]]) -> Vec<u8> {
+        let mut payload = vec![0; IFINFOMSG_LEN];
+
+        payload[4..8].copy_from_slice(&index.to_ne_bytes());
+        payload.extend(attrs.concat());
+        payload
+    }
+
+    /// Build a netlink message, padded to its alignment
+    fn message(kind: u16, payload: &[u8]) -> Vec<u8> {
+        let len = (NLMSGHDR_LEN + payload.len()) as u32;
+        let mut message =
+            [&len.to_ne_bytes()[..], &kind.to_ne_bytes()].concat();
+
+        message.resize(NLMSGHDR_LEN, 0);
+        message.extend(payload);
+        message.resize(align(message.len()), 0);
+        message
+    }
+
+    /// A link message for an interface with a name
+    fn link(kind: u16, index: i32, name: &str) -> Vec<u8> {
+        let name = [name.as_bytes(), b"\0"].concat();
+
+        message(kind, &ifinfomsg(index, &[&attr(IFLA_IFNAME, &name)]))
+    }
+
+    fn path(name: &str) -> PathBuf {
+        Path::new(CLASS).join(name)
+    }
+
+    #[test]
+    fn names() {
+        let mtu = attr(4, &1500u32.to_ne_bytes());
+        let name = attr(IFLA_IFNAME, b"eth0\0");
+        let mut truncated = ifinfomsg(2, &[&name]);
+
+        truncated.truncate(truncated.len() - 4);
+
+        let table: [(_, _, Option<&[u8]>); 7] = [
+            ("name", ifinfomsg(2, &[&name]), Some(b"eth0")),
+            ("after", ifinfomsg(2, &[&mtu, &name]), Some(b"eth0")),
+            ("missing", ifinfomsg(2, &[&mtu]), None),
+            (
+                "not utf-8",
+                ifinfomsg(2, &[&attr(3, b"\xffx")]),
+                Some(b"\xffx"),
+            ),
+            ("truncated attribute", truncated, None),
+            ("zero length", ifinfomsg(2, &[&[0; 8]]), None),
+            ("truncated ifinfomsg", vec![0; IFINFOMSG_LEN - 1], None),
+        ];
+
+        for (case, payload, expected) in table {
+            let expected = expected.map(|name| OsStr::from_bytes(name).into());
+
+            assert_eq!(Rtnetlink::name(&payload), expected, "{case}");
+        }
+    }
+
+    #[test]
+    fn messages() {
+        let newlink = link(RTM_NEWLINK, 2, "eth0");
+        let mut truncated = link(RTM_NEWLINK, 3, "wlan0");
+
+        truncated.truncate(truncated.len() - 8);
+
+        let table: [(&str, Vec<u8>, &[_], &[_], &[_]); 7] = [
+            ("added", newlink.clone(), &[], &["eth0"], &[]),
+            (
+                "added then removed",
+                [newlink.clone(), link(RTM_DELLINK, 2, "eth0")].concat(),
+                &[],
+                &[],
+                &["eth0"],
+            ),
+            (
+                "renamed",
+                link(RTM_NEWLINK, 2, "lan0"),
+                &[(2, "eth0")],
+                &["lan0"],
+                &["eth0"],
+            ),
+            ("already known", newlink.clone(), &[(2, "eth0")], &[], &[]),
+            (
+                "truncated after another",
+                [newlink.clone(), truncated.clone()].concat(),
+                &[],
+                &["eth0"],
+                &[],
+            ),
+            ("truncated", truncated, &[], &[], &[]),
+            (
+                "truncated header",
+                newlink[..NLMSGHDR_LEN - 1].into(),
+                &[],
+                &[],
+                &[],
+            ),
+        ];
+
+        for (case, datagram, known, queued, removed) in table {
+            let fd = File::open("/dev/null").unwrap().into();
+            let known = known
+                .iter()
+                .map(|(index, name)| (*index, OsString::from(name)))
+                .collect();
+            let mut rtnetlink = Rtnetlink::with_fd(fd, false, known);
+
+            rtnetlink.handle(&datagram);
+
+            let queued: Vec<_> = queued.iter().map(|name| path(name)).collect();
+            let removed: Vec<_> =
+                removed.iter().map(|name| path(name)).collect();
+
+            assert_eq!(rtnetlink.queue, queued, "{case}");
+            assert_eq!(rtnetlink.take_removed(), removed, "{case}");
+        }
+    }
+}
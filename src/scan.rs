@@ -0,0 +1,180 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    fs, io, mem,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use pasts::prelude::*;
+
+use crate::{timer::Timer, Attempt, Backend, Dir, Found, Snapshot};
+
+/// List the device nodes in directories
+pub(crate) fn list(dirs: &[Dir]) -> HashSet<PathBuf> {
+    let mut nodes = HashSet::new();
+
+    for dir in dirs.iter() {
+        let mut paths = vec![Path::new(&*dir.path).to_path_buf()];
+
+        while let Some(path) = paths.pop() {
+            let Ok(read_dir) = fs::read_dir(path) else {
+                continue;
+            };
+
+            for file in read_dir.flatten() {
+                let path = file.path();
+
+                if file.file_type().map_or(false, |kind| kind.is_dir()) {
+                    if dir.searches(&path) {
+                        paths.push(path);
+                    }
+                } else if dir.matches(&path) {
+                    nodes.insert(path);
+                }
+            }
+        }
+    }
+
+    nodes
+}
+
+/// Searcher that periodically rescans directories for new device nodes
+#[derive(Debug)]
+pub(crate) struct Scanner {
+    dirs: Vec<Dir>,
+    interval: Duration,
+    seen: HashSet<PathBuf>,
+    queue: VecDeque<PathBuf>,
+    /// Device nodes that were gone on the last scan, not yet taken
+    removed: VecDeque<PathBuf>,
+    timer: Timer,
+}
+
+impl Scanner {
+    /// Create a scanner, returns `None` if none of the directories can be read
+    pub(crate) fn new(
+        dirs: Vec<Dir>,
+        interval: Duration,
+        attempts: &mut Vec<Attempt>,
+    ) -> Option<Self> {
+        if dirs.is_empty() {
+            let error = io::Error::new(
+                io::ErrorKind::Unsupported,
+                "devices of this kind can't be found on this platform",
+            );
+
+            attempts.push(Attempt::new(Backend::Scan, None, error));
+            return None;
+        }
+
+        let mut readable = false;
+
+        for dir in dirs.iter() {
+            match fs::read_dir(&*dir.path) {
+                Ok(_) => readable = true,
+                Err(e) => attempts.push(Attempt::new(
+                    Backend::Scan,
+                    Some(&*dir.path),
+                    e,
+                )),
+            }
+        }
+
+        if !readable {
+            return None;
+        }
+
+        let seen = HashSet::new();
+        let queue = VecDeque::new();
+        let removed = VecDeque::new();
+        let mut timer = Timer::default();
+
+        // Do the initial scan on the first poll
+        timer.set(Instant::now());
+
+        Some(Self {
+            dirs,
+            interval,
+            seen,
+            queue,
+            removed,
+            timer,
+        })
+    }
+
+    /// Restore a scanner from its saved state
+    pub(crate) fn restore(
+        dirs: Vec<Dir>,
+        interval: Duration,
+        snapshot: &Snapshot,
+    ) -> Self {
+        let seen = snapshot.seen.iter().cloned().collect();
+        let queue = VecDeque::new();
+        let removed = VecDeque::new();
+        let mut timer = Timer::default();
+
+        // Check for devices added during the restart on the first poll
+        timer.set(Instant::now());
+
+        Self {
+            dirs,
+            interval,
+            seen,
+            queue,
+            removed,
+            timer,
+        }
+    }
+
+    /// Save the scanner's state
+    pub(crate) fn save(self, snapshot: &mut Snapshot) {
+        snapshot.dir = self.dirs.first().map(|dir| dir.path.to_string());
+        snapshot.pending.extend(self.queue);
+        snapshot.seen.extend(self.seen);
+    }
+
+    /// Skip the device nodes that are already there
+    pub(crate) fn skip_initial(&mut self) {
+        self.seen = list(&self.dirs);
+    }
+
+    /// Take the device nodes removed since last time
+    pub(crate) fn take_removed(&mut self) -> VecDeque<PathBuf> {
+        mem::take(&mut self.removed)
+    }
+
+    /// Queue device nodes that weren't there on the previous scan, and ones
+    /// that are gone
+    fn rescan(&mut self) {
+        let current = list(&self.dirs);
+        let mut found: Vec<_> = current.difference(&self.seen).collect();
+        let mut removed: Vec<_> = self.seen.difference(&current).collect();
+
+        found.sort();
+        removed.sort();
+        self.queue.extend(found.into_iter().cloned());
+        self.removed.extend(removed.into_iter().cloned());
+        self.seen = current;
+    }
+}
+
+impl Notify for Scanner {
+    type Event = Found;
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Found> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(path) = this.queue.pop_front() {
+                return Ready(Found::new(path));
+            }
+
+            if Pin::new(&mut this.timer).poll_next(task).is_pending() {
+                return Pending;
+            }
+
+            this.rescan();
+            this.timer.set(Instant::now() + this.interval);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    fmt, mem,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    task::Waker,
+    time::{Duration, Instant},
+};
+
+use pasts::prelude::*;
+
+use crate::{timer::Timer, Found, Searcher};
+
+/// How often to check if devices being worked on were disconnected
+const PROBE: Duration = Duration::from_secs(1);
+
+type Work = Pin<Box<dyn Future<Output = ()> + Send>>;
+type Spawner = Box<dyn FnMut(Found) -> Work + Send>;
+
+/// A worker, and the path of the device it's working on
+struct Worker {
+    path: PathBuf,
+    work: Work,
+}
+
+/// Searchers and workers running in a scope
+#[derive(Default)]
+struct Workers {
+    searchers: Vec<(Searcher, Spawner)>,
+    workers: Vec<Worker>,
+    waker: Option<Waker>,
+}
+
+/// Run `body` with a [`Scope`] for running a worker per device found.
+///
+/// Workers are cancelled when their device is disconnected, and any still
+/// running are cancelled once `body` finishes, so none outlive the scope.
+///
+/// ```rust, no_run
+/// use lookit::Searcher;
+///
+/// #[async_main::async_main]
+/// async fn main(_spawner: impl async_main::Spawn) {
+///     lookit::scope(|devices| async move {
+///         devices.spawn(Searcher::with_joystick(), |found| async move {
+///             let Ok((device, access)) = found.connect_input() else {
+///                 return;
+///             };
+///
+///             dbg!(device, access);
+///         });
+///
+///         // Run until the program is stopped
+///         std::future::pending::<()>().await;
+///     })
+///     .await;
+/// }
+/// ```
+pub async fn scope<F: Future>(body: impl FnOnce(Scope) -> F) -> F::Output {
+    let scope = Scope::default();
+    let body = Box::pin(body(scope.clone()));
+    let timer = Timer::default();
+
+    Run { body, scope, timer }.await
+}
+
+/// Handle for running device workers in a [`scope()`].
+#[derive(Clone, Default)]
+pub struct Scope(Arc<Mutex<Workers>>);
+
+impl fmt::Debug for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope").finish_non_exhaustive()
+    }
+}
+
+impl Scope {
+    /// Run a worker for each device `searcher` finds, until the device is
+    /// disconnected, the worker finishes, or the scope ends.
+    pub fn spawn<W>(
+        &self,
+        searcher: Searcher,
+        mut worker: impl FnMut(Found) -> W + Send + 'static,
+    ) where
+        W: Future<Output = ()> + Send + 'static,
+    {
+        let spawner: Spawner = Box::new(move |found| Box::pin(worker(found)));
+        let mut workers = self.0.lock().unwrap();
+
+        workers.searchers.push((searcher, spawner));
+
+        if let Some(waker) = workers.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// The future returned by [`scope()`]
+struct Run<F: Future> {
+    body: Pin<Box<F>>,
+    scope: Scope,
+    timer: Timer,
+}
+
+impl<F: Future> Future for Run<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<F::Output> {
+        let this = self.get_mut();
+
+        if let Ready(output) = this.body.as_mut().poll(task) {
+            return Ready(output);
+        }
+
+        // Searchers and workers may spawn more, so don't keep them locked
+        let (mut searchers, mut workers) = {
+            let mut shared = this.scope.0.lock().unwrap();
+
+            shared.waker = Some(task.waker().clone());
+
+            (
+                mem::take(&mut shared.searchers),
+                mem::take(&mut shared.workers),
+            )
+        };
+
+        for (searcher, spawner) in searchers.iter_mut() {
+            while let Ready(found) = Pin::new(&mut *searcher).poll_next(task) {
+                let path = found.with_path(Path::to_path_buf);
+                let work = spawner(found);
+
+                workers.push(Worker { path, work });
+            }
+        }
+
+        // Disconnected devices have their node removed
+        workers.retain_mut(|worker| {
+            worker.path.exists() && worker.work.as_mut().poll(task).is_pending()
+        });
+
+        if workers.is_empty() {
+            this.timer.clear();
+        } else {
+            if !this.timer.is_set() {
+                this.timer.set(Instant::now() + PROBE);
+            }
+
+            // Wake up to check again after a while
+            while Pin::new(&mut this.timer).poll_next(task).is_ready() {
+                this.timer.set(Instant::now() + PROBE);
+            }
+        }
+
+        let mut shared = this.scope.0.lock().unwrap();
+
+        searchers.append(&mut shared.searchers);
+        workers.append(&mut shared.workers);
+        shared.searchers = searchers;
+        shared.workers = workers;
+
+        Pending
+    }
+}
+
+impl<F: Future> Drop for Run<F> {
+    fn drop(&mut self) {
+        // Cancel the workers still running
+        *self.scope.0.lock().unwrap() = Workers::default();
+    }
+}
@@ -0,0 +1,550 @@
+use std::{fmt, str::FromStr};
+
+use serde::{
+    de::{self, IgnoredAny, MapAccess, SeqAccess, Unexpected, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{DeviceInfo, Found, Kind, Snapshot, UsbInfo};
+
+const FOUND: &[&str] = &["path", "kind", "alias"];
+const USB_INFO: &[&str] = &["vendor", "product", "serial"];
+const DEVICE_INFO: &[&str] = &["bus", "driver", "devnum", "subsystem"];
+
+/// Get a field of a struct from a sequence, in order
+fn element<'de, A, T>(
+    seq: &mut A,
+    index: usize,
+    exp: &str,
+) -> Result<T, A::Error>
+where
+    A: SeqAccess<'de>,
+    T: Deserialize<'de>,
+{
+    seq.next_element()?
+        .ok_or_else(|| de::Error::invalid_length(index, &exp))
+}
+
+/// Set a field of a struct from a map, if it's not already set
+fn field<'de, A, T>(
+    map: &mut A,
+    value: &mut Option<T>,
+    name: &'static str,
+) -> Result<(), A::Error>
+where
+    A: MapAccess<'de>,
+    T: Deserialize<'de>,
+{
+    if value.is_some() {
+        return Err(de::Error::duplicate_field(name));
+    }
+
+    *value = Some(map.next_value()?);
+
+    Ok(())
+}
+
+impl Serialize for Kind {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Kind {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct KindVisitor;
+
+        impl Visitor<'_> for KindVisitor {
+            type Value = Kind;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a kind name")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<Kind, E> {
+                Kind::from_name(name).ok_or_else(|| {
+                    E::invalid_value(Unexpected::Str(name), &self)
+                })
+            }
+        }
+
+        deserializer.deserialize_str(KindVisitor)
+    }
+}
+
+/// Devices are sent by path, so they can be connected to by the receiver.
+/// Permits from [`Searcher::limit()`](crate::Searcher::limit) aren't sent.
+impl Serialize for Found {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Found", FOUND.len())?;
+
+        state.serialize_field("path", &self.path)?;
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("alias", &self.alias)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Found {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct FoundVisitor;
+
+        impl<'de> Visitor<'de> for FoundVisitor {
+            type Value = Found;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("struct Found")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Found, A::Error> {
+                let path = element(&mut seq, 0, "struct Found")?;
+                let kind = element(&mut seq, 1, "struct Found")?;
+                let mut found = Found::new(path);
+
+                found.kind = kind;
+                found.alias = element(&mut seq, 2, "struct Found")?;
+
+                Ok(found)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Found, A::Error> {
+                let mut path = None;
+                let mut kind = None;
+                let mut alias = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "path" => field(&mut map, &mut path, "path")?,
+                        "kind" => field(&mut map, &mut kind, "kind")?,
+                        "alias" => field(&mut map, &mut alias, "alias")?,
+                        _ => drop(map.next_value::<IgnoredAny>()?),
+                    }
+                }
+
+                let path =
+                    path.ok_or_else(|| de::Error::missing_field("path"))?;
+                let mut found = Found::new(path);
+
+                // Devices that aren't of any kind may leave it out
+                found.kind = kind.flatten();
+                found.alias = alias.flatten();
+
+                Ok(found)
+            }
+        }
+
+        deserializer.deserialize_struct("Found", FOUND, FoundVisitor)
+    }
+}
+
+impl Serialize for UsbInfo {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut state =
+            serializer.serialize_struct("UsbInfo", USB_INFO.len())?;
+
+        state.serialize_field("vendor", &self.vendor)?;
+        state.serialize_field("product", &self.product)?;
+        state.serialize_field("serial", &self.serial)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for UsbInfo {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct UsbInfoVisitor;
+
+        impl<'de> Visitor<'de> for UsbInfoVisitor {
+            type Value = UsbInfo;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("struct UsbInfo")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<UsbInfo, A::Error> {
+                let vendor = element(&mut seq, 0, "struct UsbInfo")?;
+                let product = element(&mut seq, 1, "struct UsbInfo")?;
+                let serial = element(&mut seq, 2, "struct UsbInfo")?;
+
+                Ok(UsbInfo {
+                    vendor,
+                    product,
+                    serial,
+                })
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<UsbInfo, A::Error> {
+                let mut vendor = None;
+                let mut product = None;
+                let mut serial = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "vendor" => field(&mut map, &mut vendor, "vendor")?,
+                        "product" => field(&mut map, &mut product, "product")?,
+                        "serial" => field(&mut map, &mut serial, "serial")?,
+                        _ => drop(map.next_value::<IgnoredAny>()?),
+                    }
+                }
+
+                let vendor =
+                    vendor.ok_or_else(|| de::Error::missing_field("vendor"))?;
+                let product = product
+                    .ok_or_else(|| de::Error::missing_field("product"))?;
+                let serial = serial.flatten();
+
+                Ok(UsbInfo {
+                    vendor,
+                    product,
+                    serial,
+                })
+            }
+        }
+
+        deserializer.deserialize_struct("UsbInfo", USB_INFO, UsbInfoVisitor)
+    }
+}
+
+impl Serialize for DeviceInfo {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut state =
+            serializer.serialize_struct("DeviceInfo", DEVICE_INFO.len())?;
+
+        state.serialize_field("bus", &self.bus)?;
+        state.serialize_field("driver", &self.driver)?;
+        state.serialize_field("devnum", &self.devnum)?;
+        state.serialize_field("subsystem", &self.subsystem)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for DeviceInfo {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct DeviceInfoVisitor;
+
+        impl<'de> Visitor<'de> for DeviceInfoVisitor {
+            type Value = DeviceInfo;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("struct DeviceInfo")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<DeviceInfo, A::Error> {
+                let bus = element(&mut seq, 0, "struct DeviceInfo")?;
+                let driver = element(&mut seq, 1, "struct DeviceInfo")?;
+                let devnum = element(&mut seq, 2, "struct DeviceInfo")?;
+                let subsystem = element(&mut seq, 3, "struct DeviceInfo")?;
+
+                Ok(DeviceInfo {
+                    bus,
+                    driver,
+                    devnum,
+                    subsystem,
+                })
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<DeviceInfo, A::Error> {
+                let mut bus = None;
+                let mut driver = None;
+                let mut devnum = None;
+                let mut subsystem = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "bus" => field(&mut map, &mut bus, "bus")?,
+                        "driver" => field(&mut map, &mut driver, "driver")?,
+                        "devnum" => field(&mut map, &mut devnum, "devnum")?,
+                        "subsystem" => {
+                            field(&mut map, &mut subsystem, "subsystem")?
+                        }
+                        _ => drop(map.next_value::<IgnoredAny>()?),
+                    }
+                }
+
+                // Missing fields weren't known, like when not read from sysfs
+                Ok(DeviceInfo {
+                    bus: bus.flatten(),
+                    driver: driver.flatten(),
+                    devnum: devnum.flatten(),
+                    subsystem: subsystem.flatten(),
+                })
+            }
+        }
+
+        deserializer.deserialize_struct(
+            "DeviceInfo",
+            DEVICE_INFO,
+            DeviceInfoVisitor,
+        )
+    }
+}
+
+/// Snapshots are sent in their text form, like when passed across `exec()`
+impl Serialize for Snapshot {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Snapshot {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct SnapshotVisitor;
+
+        impl Visitor<'_> for SnapshotVisitor {
+            type Value = Snapshot;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a snapshot")
+            }
+
+            fn visit_str<E: de::Error>(
+                self,
+                text: &str,
+            ) -> Result<Snapshot, E> {
+                Snapshot::from_str(text).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(SnapshotVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    use super::*;
+    use crate::{Backend, Target};
+
+    /// Compares what's sent, since `Found` and `Snapshot` can't be compared
+    #[derive(Debug)]
+    struct Sent<T>(T);
+
+    impl PartialEq for Sent<Found> {
+        fn eq(&self, other: &Self) -> bool {
+            let (a, b) = (&self.0, &other.0);
+
+            a.path == b.path && a.kind == b.kind && a.alias == b.alias
+        }
+    }
+
+    impl PartialEq for Sent<Snapshot> {
+        fn eq(&self, other: &Self) -> bool {
+            self.0.to_string() == other.0.to_string()
+        }
+    }
+
+    impl<T: Serialize> Serialize for Sent<T> {
+        fn serialize<S: Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for Sent<T> {
+        fn deserialize<D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            T::deserialize(deserializer).map(Sent)
+        }
+    }
+
+    #[test]
+    fn found_round_trip() {
+        let mut found = Found::new(PathBuf::from("/dev/input/event3"));
+
+        found.kind = Some(Kind::Input);
+        found.alias = Some("keyboard".to_string());
+
+        assert_tokens(
+            &Sent(found),
+            &[
+                Token::Struct {
+                    name: "Found",
+                    len: 3,
+                },
+                Token::Str("path"),
+                Token::Str("/dev/input/event3"),
+                Token::Str("kind"),
+                Token::Some,
+                Token::Str("input"),
+                Token::Str("alias"),
+                Token::Some,
+                Token::Str("keyboard"),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn found_missing_fields() {
+        let found = Found::new(PathBuf::from("/dev/ttyUSB0"));
+
+        serde_test::assert_de_tokens(
+            &Sent(found),
+            &[
+                Token::Map { len: Some(1) },
+                Token::Str("path"),
+                Token::Str("/dev/ttyUSB0"),
+                Token::MapEnd,
+            ],
+        );
+        assert_de_tokens_error::<Sent<Found>>(
+            &[Token::Map { len: Some(0) }, Token::MapEnd],
+            "missing field `path`",
+        );
+    }
+
+    #[test]
+    fn rejects_bad_kind() {
+        assert_tokens(&Kind::Serial, &[Token::Str("serial")]);
+        assert_de_tokens_error::<Kind>(
+            &[Token::Str("toaster")],
+            "invalid value: string \"toaster\", expected a kind name",
+        );
+        assert_de_tokens_error::<Sent<Found>>(
+            &[
+                Token::Map { len: Some(2) },
+                Token::Str("path"),
+                Token::Str("/dev/input/event3"),
+                Token::Str("kind"),
+                Token::Some,
+                Token::Str("toaster"),
+            ],
+            "invalid value: string \"toaster\", expected a kind name",
+        );
+    }
+
+    #[test]
+    fn info_round_trip() {
+        let usb = UsbInfo {
+            vendor: 0x046d,
+            product: 0xc52b,
+            serial: None,
+        };
+        let device = DeviceInfo {
+            bus: Some("usb".to_string()),
+            driver: None,
+            devnum: Some((13, 67)),
+            subsystem: Some("input".to_string()),
+        };
+
+        assert_tokens(
+            &usb,
+            &[
+                Token::Struct {
+                    name: "UsbInfo",
+                    len: 3,
+                },
+                Token::Str("vendor"),
+                Token::U16(0x046d),
+                Token::Str("product"),
+                Token::U16(0xc52b),
+                Token::Str("serial"),
+                Token::None,
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &device,
+            &[
+                Token::Struct {
+                    name: "DeviceInfo",
+                    len: 4,
+                },
+                Token::Str("bus"),
+                Token::Some,
+                Token::Str("usb"),
+                Token::Str("driver"),
+                Token::None,
+                Token::Str("devnum"),
+                Token::Some,
+                Token::Tuple { len: 2 },
+                Token::U32(13),
+                Token::U32(67),
+                Token::TupleEnd,
+                Token::Str("subsystem"),
+                Token::Some,
+                Token::Str("input"),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        let mut snapshot =
+            Snapshot::new(Target::Kind(Kind::Input), Some(Backend::Inotify));
+
+        snapshot.fd = Some(3);
+        snapshot.watches.push((1, PathBuf::from("/dev/input/")));
+        snapshot.pending.push(PathBuf::from("/dev/input/event3"));
+
+        assert_tokens(
+            &Sent(snapshot),
+            &[Token::Str(
+                "lookit-snapshot 1\n\
+                 kind input\n\
+                 backend inotify\n\
+                 fd 3\n\
+                 watch 1 /dev/input/\n\
+                 pending /dev/input/event3\n",
+            )],
+        );
+    }
+
+    #[test]
+    fn rejects_bad_snapshot_kind() {
+        assert_de_tokens_error::<Sent<Snapshot>>(
+            &[Token::Str("lookit-snapshot 1\nkind toaster\n")],
+            "invalid snapshot kind",
+        );
+    }
+}
@@ -0,0 +1,360 @@
+#[cfg(any(unix, target_os = "wasi"))]
+use std::ffi::OsString;
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{Backend, Kind, Target};
+
+/// Header line identifying the format of a saved [`Snapshot`]
+const HEADER: &str = "lookit-snapshot 1";
+
+/// Get the bytes of a path, which aren't necessarily UTF-8
+#[cfg(any(unix, target_os = "wasi"))]
+fn to_bytes(path: &Path) -> Vec<u8> {
+    #[cfg(unix)]
+    use std::os::unix::ffi::OsStrExt;
+    #[cfg(target_os = "wasi")]
+    use std::os::wasi::ffi::OsStrExt;
+
+    path.as_os_str().as_bytes().to_vec()
+}
+
+/// Get the bytes of a path, which are UTF-8 on other platforms
+#[cfg(not(any(unix, target_os = "wasi")))]
+fn to_bytes(path: &Path) -> Vec<u8> {
+    path.to_string_lossy().into_owned().into_bytes()
+}
+
+/// Make a path from its bytes
+#[cfg(any(unix, target_os = "wasi"))]
+fn from_bytes(bytes: Vec<u8>) -> Option<PathBuf> {
+    #[cfg(unix)]
+    use std::os::unix::ffi::OsStringExt;
+    #[cfg(target_os = "wasi")]
+    use std::os::wasi::ffi::OsStringExt;
+
+    Some(OsString::from_vec(bytes).into())
+}
+
+/// Make a path from its bytes, which must be UTF-8 on other platforms
+#[cfg(not(any(unix, target_os = "wasi")))]
+fn from_bytes(bytes: Vec<u8>) -> Option<PathBuf> {
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+/// Escape text so it fits on one line, and can't be mistaken for other fields
+///
+/// Backslashes, control characters and bytes that aren't valid UTF-8 are
+/// written as `\xNN`, so names that aren't UTF-8 round-trip.
+pub(crate) fn escape(bytes: &[u8]) -> String {
+    let mut escaped = String::with_capacity(bytes.len());
+    let mut rest = bytes;
+    let escape_byte = |escaped: &mut String, byte: u8| {
+        escaped.push_str(&format!("\\x{byte:02x}"));
+    };
+
+    while !rest.is_empty() {
+        let (valid, invalid) = match std::str::from_utf8(rest) {
+            Ok(valid) => (valid, &[][..]),
+            Err(error) => {
+                let (valid, after) = rest.split_at(error.valid_up_to());
+                let len = error.error_len().unwrap_or(after.len());
+
+                (
+                    std::str::from_utf8(valid).unwrap_or_default(),
+                    &after[..len],
+                )
+            }
+        };
+
+        for c in valid.chars() {
+            if c == '\\' || c.is_control() {
+                for byte in c.to_string().bytes() {
+                    escape_byte(&mut escaped, byte);
+                }
+            } else {
+                escaped.push(c);
+            }
+        }
+
+        for &byte in invalid {
+            escape_byte(&mut escaped, byte);
+        }
+
+        rest = &rest[valid.len() + invalid.len()..];
+    }
+
+    escaped
+}
+
+/// Undo [`escape()`]
+pub(crate) fn unescape(escaped: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(escaped.len());
+    let mut rest = escaped.as_bytes();
+
+    while let Some((&byte, after)) = rest.split_first() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            rest = after;
+            continue;
+        }
+
+        let hex = after.strip_prefix(b"x")?.get(..2)?;
+        let hex = std::str::from_utf8(hex).ok()?;
+
+        bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        rest = &after[3..];
+    }
+
+    Some(bytes)
+}
+
+/// Escape a path, see [`escape()`]
+fn escape_path(path: &Path) -> String {
+    escape(&to_bytes(path))
+}
+
+/// Undo [`escape_path()`]
+fn unescape_path(escaped: &str) -> Option<PathBuf> {
+    from_bytes(unescape(escaped)?)
+}
+
+/// State of a [`Searcher`](crate::Searcher) saved so it can be restored after
+/// an `exec()` (like a systemd-style daemon reload) without missing devices.
+///
+/// Converts to and from text with [`Display`](fmt::Display) and [`FromStr`],
+/// to be passed to the new process image (for example, in an environment
+/// variable).  The file descriptor the searcher was watching with (if any) is
+/// left open across `exec()`, or can be handed to systemd's file descriptor
+/// store and replaced with [`Snapshot::set_fd()`] once received back.  With
+/// the `serde` feature, it's serialized as the same text.
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    pub(crate) target: Target,
+    pub(crate) backend: Option<Backend>,
+    /// Directory being searched
+    pub(crate) dir: Option<String>,
+    /// File descriptor being watched with
+    pub(crate) fd: Option<i32>,
+    /// Inotify watch descriptors, and the directory paths they watch
+    pub(crate) watches: Vec<(i32, PathBuf)>,
+    /// Network interface indices, and the paths of the interfaces
+    pub(crate) interfaces: Vec<(i32, PathBuf)>,
+    /// Devices found, but not yet returned by the searcher
+    pub(crate) pending: Vec<PathBuf>,
+    /// Devices already known to the backend (seen on the last scan, or
+    /// connected)
+    pub(crate) seen: Vec<PathBuf>,
+}
+
+impl Snapshot {
+    pub(crate) fn new(target: Target, backend: Option<Backend>) -> Self {
+        let dir = None;
+        let fd = None;
+        let watches = Vec::new();
+        let interfaces = Vec::new();
+        let pending = Vec::new();
+        let seen = Vec::new();
+
+        Self {
+            target,
+            backend,
+            dir,
+            fd,
+            watches,
+            interfaces,
+            pending,
+            seen,
+        }
+    }
+
+    /// Get the file descriptor that must stay open for the searcher to be
+    /// restored, if any.
+    pub fn fd(&self) -> Option<i32> {
+        self.fd
+    }
+
+    /// Replace the file descriptor, for when it's been passed back with a
+    /// different number (like from systemd's file descriptor store).
+    pub fn set_fd(&mut self, fd: i32) {
+        if self.fd.is_some() {
+            self.fd = Some(fd);
+        }
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{HEADER}")?;
+        writeln!(f, "kind {}", self.target.name())?;
+
+        if let Some(backend) = self.backend {
+            writeln!(f, "backend {backend}")?;
+        }
+
+        if let Some(ref dir) = self.dir {
+            writeln!(f, "dir {}", escape(dir.as_bytes()))?;
+        }
+
+        if let Some(fd) = self.fd {
+            writeln!(f, "fd {fd}")?;
+        }
+
+        for (wd, path) in self.watches.iter() {
+            writeln!(f, "watch {wd} {}", escape_path(path))?;
+        }
+
+        for (index, path) in self.interfaces.iter() {
+            writeln!(f, "interface {index} {}", escape_path(path))?;
+        }
+
+        for path in self.pending.iter() {
+            writeln!(f, "pending {}", escape_path(path))?;
+        }
+
+        for path in self.seen.iter() {
+            writeln!(f, "seen {}", escape_path(path))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Snapshot {
+    type Err = io::Error;
+
+    fn from_str(text: &str) -> io::Result<Self> {
+        let invalid = |what: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid snapshot {what}"),
+            )
+        };
+        let mut lines = text.lines();
+
+        if lines.next() != Some(HEADER) {
+            return Err(invalid("header"));
+        }
+
+        let kind = lines
+            .next()
+            .and_then(|line| line.strip_prefix("kind "))
+            .and_then(Kind::from_name)
+            .ok_or_else(|| invalid("kind"))?;
+        let mut snapshot = Self::new(Target::Kind(kind), None);
+
+        for line in lines {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let path = || unescape_path(value).ok_or_else(|| invalid("path"));
+
+            match key {
+                "backend" => {
+                    snapshot.backend = Some(match value {
+                        "inotify" => Backend::Inotify,
+                        "uevents" => Backend::Uevent,
+                        "rtnetlink" => Backend::Rtnetlink,
+                        "scanning" => Backend::Scan,
+                        _ => return Err(invalid("backend")),
+                    })
+                }
+                "dir" => {
+                    let dir = unescape(value)
+                        .and_then(|dir| String::from_utf8(dir).ok());
+
+                    snapshot.dir = Some(dir.ok_or_else(|| invalid("dir"))?);
+                }
+                "fd" => {
+                    snapshot.fd =
+                        Some(value.parse().map_err(|_| invalid("fd"))?)
+                }
+                "watch" | "interface" => {
+                    let (number, path) =
+                        value.split_once(' ').ok_or_else(|| invalid(key))?;
+                    let number = number.parse().map_err(|_| invalid(key))?;
+                    let path =
+                        unescape_path(path).ok_or_else(|| invalid("path"))?;
+
+                    if key == "watch" {
+                        snapshot.watches.push((number, path));
+                    } else {
+                        snapshot.interfaces.push((number, path));
+                    }
+                }
+                "pending" => snapshot.pending.push(path()?),
+                "seen" => snapshot.seen.push(path()?),
+                _ => return Err(invalid("line")),
+            }
+        }
+
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(bytes: &[u8]) {
+        let escaped = escape(bytes);
+
+        assert!(!escaped.contains(['\n', '\r', '\t']));
+        assert_eq!(unescape(&escaped).as_deref(), Some(bytes));
+    }
+
+    #[test]
+    fn escapes_spaces() {
+        round_trip(b"/dev/input/by-id/usb-Some Vendor Pad-event-joystick");
+        assert_eq!(escape(b"/dev/a b"), "/dev/a b");
+    }
+
+    #[test]
+    fn escapes_newlines() {
+        round_trip(b"/dev/evil\nseen /dev/other");
+        round_trip(b"\r\n\t");
+        assert_eq!(escape(b"a\nb"), "a\\x0ab");
+    }
+
+    #[test]
+    fn escapes_backslashes() {
+        round_trip(b"/dev/a\\x41");
+        round_trip(b"\\\\");
+        assert_eq!(escape(b"\\"), "\\x5c");
+    }
+
+    #[test]
+    fn escapes_non_utf8() {
+        round_trip(b"/dev/caf\xe9");
+        round_trip(b"\xff\xfe/\x80");
+        round_trip(b"/dev/\xe2\x82");
+        round_trip("/dev/café\u{85}".as_bytes());
+        assert_eq!(escape(b"\xff"), "\\xff");
+    }
+
+    #[test]
+    fn rejects_bad_escapes() {
+        assert_eq!(unescape("\\"), None);
+        assert_eq!(unescape("\\x4"), None);
+        assert_eq!(unescape("\\y41"), None);
+        assert_eq!(unescape("\\xzz"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keeps_non_utf8_paths() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(std::ffi::OsStr::from_bytes(b"/dev/a b\n\xff"));
+        let mut snapshot = Snapshot::new(Target::Kind(Kind::Input), None);
+
+        snapshot.pending.push(path.to_path_buf());
+        snapshot.seen.push(path.to_path_buf());
+
+        let snapshot: Snapshot = snapshot.to_string().parse().unwrap();
+
+        assert_eq!(snapshot.pending, [path]);
+        assert_eq!(snapshot.seen, [path]);
+    }
+}
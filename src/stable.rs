@@ -0,0 +1,139 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use pasts::prelude::*;
+
+use crate::{timer::Timer, Found, Interface, Platform};
+
+/// How long to wait for the device manager to create a stable path for a new
+/// device node, before reporting it by its node
+const WAIT: Duration = Duration::from_secs(1);
+
+/// How often to look for the stable path of a device node that's waiting
+const RETRY: Duration = Duration::from_millis(50);
+
+/// A device waiting for its stable path to be created
+#[derive(Debug)]
+struct Linking {
+    deadline: Instant,
+    found: Found,
+}
+
+/// Puts stable paths on found devices, waiting for them to be created
+#[derive(Debug, Default)]
+pub(crate) struct Stable {
+    /// Stable paths of devices delivered, by device node
+    links: HashMap<PathBuf, PathBuf>,
+    ready: VecDeque<Found>,
+    linking: Vec<Linking>,
+    timer: Timer,
+}
+
+impl Stable {
+    /// Add a found device, holding it back until its stable path exists
+    pub(crate) fn add(&mut self, found: Found) {
+        let deadline = Instant::now() + WAIT;
+
+        if let Some(found) = self.link(found) {
+            self.linking.push(Linking { deadline, found });
+        }
+    }
+
+    /// Report removed devices by the stable path they were delivered with,
+    /// and forget ones removed before they got one
+    pub(crate) fn remove(&mut self, paths: &mut VecDeque<PathBuf>) {
+        self.linking
+            .retain(|linking| !paths.contains(&linking.found.path));
+
+        for path in paths.iter_mut() {
+            if let Some(stable) = self.links.remove(path) {
+                *path = stable;
+            }
+        }
+    }
+
+    /// Discard the devices that are still waiting
+    pub(crate) fn clear(&mut self) {
+        self.ready.clear();
+        self.linking.clear();
+        self.timer.clear();
+    }
+
+    /// Take the devices that haven't been delivered yet, in the order they
+    /// were found
+    pub(crate) fn into_pending(self) -> impl Iterator<Item = Found> {
+        let mut pending: Vec<Found> = self.ready.into();
+
+        pending.extend(self.linking.into_iter().map(|linking| linking.found));
+        pending.sort_by_key(|found| found.timestamp);
+        pending.into_iter()
+    }
+
+    /// Make a device ready if it has a stable path, or won't be getting one,
+    /// giving it back otherwise
+    fn link(&mut self, mut found: Found) -> Option<Found> {
+        let stable = found.with_path(Platform::stable_path);
+
+        if stable.is_none() && !found.with_path(Platform::settled) {
+            return Some(found);
+        }
+
+        if let Some(ref stable) = stable {
+            self.links.insert(found.path.clone(), stable.clone());
+        }
+
+        found.stable = stable.map(PathBuf::into_boxed_path);
+
+        self.ready.push_back(found);
+        None
+    }
+}
+
+impl Notify for Stable {
+    type Event = Found;
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Found> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(found) = this.ready.pop_front() {
+                return Ready(found);
+            }
+
+            if this.linking.is_empty() {
+                this.timer.clear();
+                return Pending;
+            }
+
+            let now = Instant::now();
+
+            for linking in std::mem::take(&mut this.linking) {
+                if let Some(found) = this.link(linking.found) {
+                    // Reported by its node, where the link never came
+                    if linking.deadline <= now {
+                        this.ready.push_back(found);
+                        continue;
+                    }
+
+                    this.linking.push(Linking {
+                        deadline: linking.deadline,
+                        found,
+                    });
+                }
+            }
+
+            if !this.ready.is_empty() {
+                continue;
+            }
+
+            this.timer.set(now + RETRY);
+
+            if Pin::new(&mut this.timer).poll_next(task).is_pending() {
+                return Pending;
+            }
+        }
+    }
+}
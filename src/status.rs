@@ -0,0 +1,176 @@
+use std::{fmt, io};
+
+use crate::Backend;
+
+/// A backend that couldn't be used by a [`Searcher`](crate::Searcher).
+#[derive(Debug)]
+pub struct Attempt {
+    backend: Backend,
+    path: Option<String>,
+    error: io::Error,
+}
+
+impl Attempt {
+    pub(crate) fn new(
+        backend: Backend,
+        path: Option<&str>,
+        error: io::Error,
+    ) -> Self {
+        let path = path.map(str::to_string);
+
+        Self {
+            backend,
+            path,
+            error,
+        }
+    }
+
+    /// Get the backend that was attempted.
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// Get the path that was attempted, if the backend uses one.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Get the reason the backend couldn't be used.
+    pub fn error(&self) -> &io::Error {
+        &self.error
+    }
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.backend)?;
+
+        if let Some(ref path) = self.path {
+            write!(f, " on {path}")?;
+        }
+
+        write!(f, ": {}", self.error)
+    }
+}
+
+/// A sandbox restriction detected while setting up a
+/// [`Searcher`](crate::Searcher).
+///
+/// Displays as a hint for which permission is missing, suitable for showing
+/// to users.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Restriction {
+    /// `/dev` can't be read
+    Devices,
+    /// Creating inotify instances is blocked (for example, by seccomp)
+    Inotify,
+    /// Netlink uevent sockets are blocked (for example, by seccomp)
+    Netlink,
+    /// `/sys` is hidden, so devices can't be grouped or described
+    Sysfs,
+}
+
+impl fmt::Display for Restriction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Devices => {
+                "/dev can't be read, the sandbox needs access to device files"
+            }
+            Self::Inotify => {
+                "inotify is blocked (likely by seccomp), devices will be found \
+                 more slowly"
+            }
+            Self::Netlink => {
+                "netlink uevents are blocked (likely by seccomp or a network \
+                 namespace)"
+            }
+            Self::Sysfs => {
+                "/sys is hidden, the sandbox needs it mounted to group or \
+                 describe devices"
+            }
+        })
+    }
+}
+
+/// Diagnostics describing how a [`Searcher`](crate::Searcher) was set up.
+#[derive(Debug)]
+pub struct Status {
+    backend: Option<Backend>,
+    attempts: Vec<Attempt>,
+    restrictions: Vec<Restriction>,
+}
+
+impl Status {
+    pub(crate) fn new(
+        backend: Option<Backend>,
+        attempts: Vec<Attempt>,
+        restrictions: Vec<Restriction>,
+    ) -> Self {
+        Self {
+            backend,
+            attempts,
+            restrictions,
+        }
+    }
+
+    /// Stop using the backend, once the searcher is closed
+    pub(crate) fn close(&mut self) {
+        self.backend = None;
+    }
+
+    /// Get the backend in use, or `None` if no backend could be used (or the
+    /// searcher was closed).
+    pub fn backend(&self) -> Option<Backend> {
+        self.backend
+    }
+
+    /// Check whether or not the searcher will ever find any devices.
+    pub fn is_active(&self) -> bool {
+        self.backend.is_some()
+    }
+
+    /// Get the backends that were attempted and couldn't be used, in the
+    /// order they were tried.
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Get the sandbox restrictions that were detected, which may explain
+    /// why backends couldn't be used or why devices can't be found.
+    pub fn restrictions(&self) -> &[Restriction] {
+        &self.restrictions
+    }
+}
+
+impl std::error::Error for Status {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        let attempt = self.attempts.last()?;
+
+        Some(&attempt.error)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.backend {
+            Some(backend) => write!(f, "searching with {backend}")?,
+            None => f.write_str("not searching")?,
+        }
+
+        for (i, attempt) in self.attempts.iter().enumerate() {
+            f.write_str(if i == 0 { " (tried " } else { ", " })?;
+            write!(f, "{attempt}")?;
+        }
+
+        if !self.attempts.is_empty() {
+            f.write_str(")")?;
+        }
+
+        for restriction in self.restrictions.iter() {
+            write!(f, "; {restriction}")?;
+        }
+
+        Ok(())
+    }
+}
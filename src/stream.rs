@@ -0,0 +1,100 @@
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+use pasts::notify::Notify;
+
+use crate::{Found, Searcher};
+
+/// Lookit [`Stream`].  Yields each device [`Found`] by a searcher.
+///
+/// Created with [`Searcher::into_stream()`].  Never ends, so stop polling once
+/// enough devices are found.
+pub struct SearchStream {
+    searcher: Searcher,
+}
+
+impl fmt::Debug for SearchStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchStream")
+            .field("searcher", &self.searcher)
+            .finish()
+    }
+}
+
+impl SearchStream {
+    pub(crate) fn new(searcher: Searcher) -> Self {
+        Self { searcher }
+    }
+
+    /// Get the searcher back.
+    pub fn into_inner(self) -> Searcher {
+        self.searcher
+    }
+}
+
+impl Stream for SearchStream {
+    type Item = Found;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Found>> {
+        Pin::new(&mut self.get_mut().searcher)
+            .poll_next(cx)
+            .map(Some)
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::{fs, sync::Arc, thread};
+
+    use super::*;
+    use crate::blocking::Unpark;
+
+    /// Block the thread until the stream yields
+    fn next(stream: &mut SearchStream) -> Option<Found> {
+        let waker = Arc::new(Unpark(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            if let Poll::Ready(item) = Pin::new(&mut *stream).poll_next(&mut cx)
+            {
+                return item;
+            }
+
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn yields_devices() {
+        let dir = std::env::temp_dir()
+            .join(format!("lookit-stream-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("dev0"), "").unwrap();
+
+        let path = format!("{}/", dir.display());
+        let mut stream = Searcher::with_path(path, ["dev"]).into_stream();
+        let initial = next(&mut stream).unwrap();
+
+        // Added while the task is waiting
+        let node = dir.join("dev1");
+        let adding = node.clone();
+        let adder = thread::spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(100));
+            fs::write(adding, "").unwrap();
+        });
+        let added = next(&mut stream).unwrap();
+
+        adder.join().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(initial.path(), dir.join("dev0"));
+        assert_eq!(added.path(), node);
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io,
+    mem::{size_of, MaybeUninit},
+    os::{
+        fd::{AsRawFd, RawFd},
+        raw::{c_char, c_int, c_ulong, c_void},
+        unix::fs::OpenOptionsExt,
+    },
+    path::Path,
+};
+
+use super::sysfs;
+use crate::{Feature, VideoFormat, VideoInfo};
+
+/// struct utsname, from C.
+#[repr(C)]
+struct Utsname {
+    sysname: [c_char; 65],
+    nodename: [c_char; 65],
+    release: [c_char; 65],
+    version: [c_char; 65],
+    machine: [c_char; 65],
+    domainname: [c_char; 65],
+}
+
+/// struct v4l2_capability, from C.
+#[repr(C)]
+struct V4l2Capability {
+    driver: [u8; 16],
+    card: [u8; 32],
+    bus_info: [u8; 32],
+    version: u32,
+    capabilities: u32,
+    device_caps: u32,
+    reserved: [u32; 3],
+}
+
+/// struct v4l2_pix_format, from C.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct V4l2PixFormat {
+    width: u32,
+    height: u32,
+    pixelformat: u32,
+    field: u32,
+    bytesperline: u32,
+    sizeimage: u32,
+    colorspace: u32,
+    priv_: u32,
+    flags: u32,
+    ycbcr_enc: u32,
+    quantization: u32,
+    xfer_func: u32,
+}
+
+/// struct v4l2_plane_pix_format, from C.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct V4l2PlanePixFormat {
+    sizeimage: u32,
+    bytesperline: u32,
+    reserved: [u16; 6],
+}
+
+/// struct v4l2_pix_format_mplane, from C.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct V4l2PixFormatMplane {
+    width: u32,
+    height: u32,
+    pixelformat: u32,
+    field: u32,
+    colorspace: u32,
+    plane_fmt: [V4l2PlanePixFormat; 8],
+    num_planes: u8,
+    flags: u8,
+    ycbcr_enc: u8,
+    quantization: u8,
+    xfer_func: u8,
+    reserved: [u8; 7],
+}
+
+/// The union in struct v4l2_format, from C.  Other members have pointers, so
+/// it's aligned like one.
+#[repr(C)]
+union V4l2FormatUnion {
+    pix: V4l2PixFormat,
+    pix_mp: V4l2PixFormatMplane,
+    raw_data: [u8; 200],
+    align: [usize; 0],
+}
+
+/// struct v4l2_format, from C.
+#[repr(C)]
+struct V4l2Format {
+    type_: u32,
+    fmt: V4l2FormatUnion,
+}
+
+// https://github.com/torvalds/linux/blob/dbad9ce9397ef7f891b4ff44bad694add673c1a1/include/uapi/linux/videodev2.h
+
+const VIDIOC_QUERYCAP: c_ulong = 0x80685600;
+/// `_IOWR('V', 4, struct v4l2_format)`, which has a different size on 32-bit
+const VIDIOC_G_FMT: c_ulong =
+    0xc000_5604 | (size_of::<V4l2Format>() as c_ulong) << 16;
+const V4L2_CAP_VIDEO_CAPTURE: u32 = 0x00000001;
+const V4L2_CAP_VIDEO_CAPTURE_MPLANE: u32 = 0x00001000;
+const V4L2_CAP_DEVICE_CAPS: u32 = 0x80000000;
+const V4L2_BUF_TYPE_VIDEO_CAPTURE: u32 = 1;
+const V4L2_BUF_TYPE_VIDEO_CAPTURE_MPLANE: u32 = 9;
+
+extern "C" {
+    fn uname(buf: *mut Utsname) -> c_int;
+    fn ioctl(fd: RawFd, req: c_ulong, arg: *mut c_void) -> c_int;
+}
+
+/// Check if the running kernel is at least a version
+fn kernel_at_least(major: u32, minor: u32) -> bool {
+    let mut utsname = MaybeUninit::uninit();
+
+    if unsafe { uname(utsname.as_mut_ptr()) } == -1 {
+        return false;
+    }
+
+    let utsname = unsafe { utsname.assume_init() };
+    let release: Vec<u8> = utsname
+        .release
+        .iter()
+        .take_while(|c| **c != 0)
+        .map(|c| *c as u8)
+        .collect();
+    let release = String::from_utf8_lossy(&release);
+    let mut numbers = release
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|number| number.parse::<u32>().unwrap_or(0));
+    let version = (numbers.next().unwrap_or(0), numbers.next().unwrap_or(0));
+
+    version >= (major, minor)
+}
+
+/// Open a V4L2 device node without connecting to it, to query it
+fn open_video(path: &Path) -> io::Result<File> {
+    OpenOptions::new().read(true).custom_flags(2048).open(path)
+}
+
+/// Get what a V4L2 device's driver reports about it
+fn query_cap(file: &File) -> io::Result<V4l2Capability> {
+    let mut cap = MaybeUninit::<V4l2Capability>::uninit();
+    let ptr = cap.as_mut_ptr().cast();
+
+    if unsafe { ioctl(file.as_raw_fd(), VIDIOC_QUERYCAP, ptr) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(unsafe { cap.assume_init() })
+}
+
+/// Get the capabilities of the device node, rather than of the whole device
+/// when the driver reports them
+fn device_caps(cap: &V4l2Capability) -> u32 {
+    if cap.capabilities & V4L2_CAP_DEVICE_CAPS != 0 {
+        cap.device_caps
+    } else {
+        cap.capabilities
+    }
+}
+
+/// Get the video capabilities of a V4L2 device
+fn video_caps(path: &Path) -> Option<u32> {
+    let file = open_video(path).ok()?;
+
+    query_cap(&file).ok().as_ref().map(device_caps)
+}
+
+/// Get a string from a fixed-size, nul-padded C array
+fn c_string(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+/// Get the capture metadata of a V4L2 camera
+pub(super) fn video_info(path: &Path) -> io::Result<VideoInfo> {
+    let file = open_video(path)?;
+    let cap = query_cap(&file)?;
+    let capabilities = device_caps(&cap);
+    let type_ = if capabilities & V4L2_CAP_VIDEO_CAPTURE != 0 {
+        V4L2_BUF_TYPE_VIDEO_CAPTURE
+    } else if capabilities & V4L2_CAP_VIDEO_CAPTURE_MPLANE != 0 {
+        V4L2_BUF_TYPE_VIDEO_CAPTURE_MPLANE
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "not a video capture device",
+        ));
+    };
+    let fmt = V4l2FormatUnion { raw_data: [0; 200] };
+    let mut format = V4l2Format { type_, fmt };
+    let ptr: *mut V4l2Format = &mut format;
+
+    if unsafe { ioctl(file.as_raw_fd(), VIDIOC_G_FMT, ptr.cast()) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let format = if type_ == V4L2_BUF_TYPE_VIDEO_CAPTURE {
+        // Filled in for this buffer type
+        let pix = unsafe { format.fmt.pix };
+
+        VideoFormat {
+            width: pix.width,
+            height: pix.height,
+            fourcc: pix.pixelformat.to_le_bytes(),
+            bytes_per_line: pix.bytesperline,
+            size_image: pix.sizeimage,
+            planes: 1,
+        }
+    } else {
+        // Filled in for this buffer type
+        let pix = unsafe { format.fmt.pix_mp };
+        let planes = pix.num_planes.min(8);
+
+        VideoFormat {
+            width: pix.width,
+            height: pix.height,
+            fourcc: pix.pixelformat.to_le_bytes(),
+            bytes_per_line: pix.plane_fmt[0].bytesperline,
+            size_image: pix.plane_fmt[..planes.into()]
+                .iter()
+                .map(|plane| plane.sizeimage)
+                .sum(),
+            planes,
+        }
+    };
+
+    Ok(VideoInfo {
+        driver: c_string(&cap.driver),
+        card: c_string(&cap.card),
+        capabilities,
+        format,
+    })
+}
+
+/// Check if an input device's capability bitmap has any bits set
+fn input_caps(path: &Path, name: &str) -> bool {
+    let Some(node) = sysfs::node(path) else {
+        return false;
+    };
+    let Ok(bits) =
+        fs::read_to_string(node.join("device/capabilities").join(name))
+    else {
+        return false;
+    };
+
+    bits.split_whitespace()
+        .any(|word| u64::from_str_radix(word, 16).map_or(false, |n| n != 0))
+}
+
+/// Check if a device node supports a kernel driver feature
+pub(super) fn supports(path: &Path, feature: Feature) -> bool {
+    let is_input = path.to_string_lossy().starts_with("/dev/input/event");
+
+    match feature {
+        // EVIOCREVOKE was added in Linux 3.12
+        Feature::Revoke => is_input && kernel_at_least(3, 12),
+        Feature::ForceFeedback => is_input && input_caps(path, "ff"),
+        Feature::Capture => video_caps(path)
+            .map_or(false, |caps| caps & V4L2_CAP_VIDEO_CAPTURE != 0),
+        Feature::Multiplanar => video_caps(path)
+            .map_or(false, |caps| caps & V4L2_CAP_VIDEO_CAPTURE_MPLANE != 0),
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v4l2_format_layout() {
+        // The union is 200 bytes, after the type padded to its alignment
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(VIDIOC_G_FMT, 0xc0d05604);
+        #[cfg(target_pointer_width = "32")]
+        assert_eq!(VIDIOC_G_FMT, 0xc0cc5604);
+        assert_eq!(size_of::<V4l2PixFormat>(), 48);
+        assert_eq!(size_of::<V4l2PixFormatMplane>(), 192);
+    }
+
+    #[test]
+    fn c_strings() {
+        assert_eq!(c_string(b"uvcvideo\0\0\0\0\0\0\0\0"), "uvcvideo");
+        assert_eq!(c_string(b"full"), "full");
+    }
+}
@@ -0,0 +1,376 @@
+use std::{
+    fs,
+    os::unix::fs::{FileTypeExt, MetadataExt},
+    path::{Path, PathBuf},
+};
+
+use crate::{DeviceInfo, UsbInfo};
+
+/// Get the sysfs directory for a device node
+pub(super) fn node(path: &Path) -> Option<PathBuf> {
+    // Devices without nodes are found by their sysfs directory
+    if path.starts_with("/sys/") {
+        return fs::canonicalize(path).ok();
+    }
+
+    let (kind, major, minor) = number(path)?;
+
+    fs::canonicalize(format!("/sys/dev/{kind}/{major}:{minor}")).ok()
+}
+
+/// Get the kind (`"block"` or `"char"`) and major and minor numbers of a
+/// device node
+pub(super) fn number(path: &Path) -> Option<(&'static str, u64, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    let kind = if metadata.file_type().is_block_device() {
+        "block"
+    } else if metadata.file_type().is_char_device() {
+        "char"
+    } else {
+        return None;
+    };
+    let (major, minor) = split(metadata.rdev());
+
+    Some((kind, major, minor))
+}
+
+/// Split a device number into its major and minor numbers, like glibc's
+/// `gnu_dev_major()` and `gnu_dev_minor()`
+// https://github.com/bminor/glibc/blob/glibc-2.37/bits/sysmacros.h
+fn split(rdev: u64) -> (u64, u64) {
+    let major = ((rdev >> 8) & 0xfff) | ((rdev >> 32) & 0xffff_f000);
+    let minor = (rdev & 0xff) | ((rdev >> 12) & 0xffff_ff00);
+
+    (major, minor)
+}
+
+/// Get the sysfs directory for the physical device a node belongs to
+///
+/// This is the USB device if there is one, otherwise the node's parent device.
+pub(super) fn parent(path: &Path) -> Option<PathBuf> {
+    if let Some(usb) = usb(path) {
+        return Some(usb);
+    }
+
+    fs::canonicalize(node(path)?.join("device")).ok()
+}
+
+/// Get the sysfs directory for the USB device a node belongs to
+fn usb(path: &Path) -> Option<PathBuf> {
+    let node = node(path)?;
+    let usb = node.ancestors().find(|dir| dir.join("idVendor").exists())?;
+
+    Some(usb.to_path_buf())
+}
+
+/// Get the port path of the USB device a node belongs to
+///
+/// USB device directories are named after the bus and chain of hub ports
+/// (like `1-2.3`).
+pub(super) fn port(path: &Path) -> Option<String> {
+    let usb = usb(path)?;
+    let name = usb.file_name()?.to_str()?;
+
+    Some(format!("usb-port:{name}"))
+}
+
+/// Check if the USB device a node belongs to has an interface of a class
+///
+/// Interface directories are named after the device, configuration and
+/// interface number (like `1-2.3:1.0`).
+pub(super) fn usb_class(path: &Path, class: u8) -> bool {
+    let Some(usb) = usb(path) else {
+        return false;
+    };
+
+    fs::read_dir(usb)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            fs::read_to_string(entry.path().join("bInterfaceClass")).ok()
+        })
+        .any(|value| u8::from_str_radix(value.trim(), 16) == Ok(class))
+}
+
+/// Read a sysfs attribute, without surrounding whitespace
+fn read(path: PathBuf) -> Option<String> {
+    let value = fs::read_to_string(path).ok()?;
+    let value = value.trim();
+
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Get the index of a device node among the ones of its kind on the same
+/// physical device, which is 0 for a camera's video (not metadata) node
+pub(super) fn index(path: &Path) -> Option<u32> {
+    read(node(path)?.join("index"))?.parse().ok()
+}
+
+/// Get the name of an ALSA sound card from its number
+fn card_name(number: &str) -> Option<String> {
+    let cards = fs::read_to_string("/proc/asound/cards").ok()?;
+
+    // Lines are like " 0 [PCH            ]: HDA-Intel - HDA Intel PCH"
+    cards.lines().find_map(|line| {
+        let (index, rest) = line.trim_start().split_once(' ')?;
+        let (_driver, name) = rest.split_once("]: ")?.1.split_once(" - ")?;
+
+        (index == number).then(|| name.trim().to_string())
+    })
+}
+
+/// Get a human-readable name for a device node
+///
+/// This is the name its driver gives it (for cameras and input devices), its
+/// card's name (for sound devices), or else the name of the HID or USB device
+/// it belongs to.
+pub(super) fn name(path: &Path) -> Option<String> {
+    let node = node(path)?;
+
+    if path.starts_with("/dev/snd/") {
+        let card = node.join("device");
+
+        return read(card.join("number"))
+            .and_then(|number| card_name(&number))
+            .or_else(|| read(card.join("id")));
+    }
+
+    if let Some(name) = read(node.join("name")) {
+        return Some(name);
+    }
+
+    if let Some(name) = read(node.join("device/name")) {
+        return Some(name);
+    }
+
+    let uevent =
+        fs::read_to_string(node.join("device/uevent")).unwrap_or_default();
+
+    if let Some(name) = uevent
+        .lines()
+        .find_map(|line| line.strip_prefix("HID_NAME="))
+    {
+        return Some(name.to_string());
+    }
+
+    read(usb(path)?.join("product"))
+}
+
+/// Get the name of the directory a sysfs link points to
+fn link_name(path: PathBuf) -> Option<String> {
+    fs::read_link(path)
+        .ok()?
+        .file_name()?
+        .to_str()
+        .map(str::to_string)
+}
+
+/// Get the bus, driver, device number and subsystem of a device node
+pub(super) fn device_info(path: &Path) -> DeviceInfo {
+    let Some(node) = node(path) else {
+        return DeviceInfo::default();
+    };
+    // Class devices (like input event nodes) have a parent on a bus
+    let devices = || {
+        node.ancestors()
+            .take_while(|dir| dir.starts_with("/sys/devices/"))
+    };
+    let bus = devices().find_map(|dir| {
+        let subsystem = fs::canonicalize(dir.join("subsystem")).ok()?;
+
+        subsystem
+            .starts_with("/sys/bus/")
+            .then(|| subsystem.file_name()?.to_str().map(str::to_string))
+            .flatten()
+    });
+    let driver = devices().find_map(|dir| link_name(dir.join("driver")));
+    let devnum = read(node.join("dev")).and_then(|dev| {
+        let (major, minor) = dev.split_once(':')?;
+
+        Some((major.parse().ok()?, minor.parse().ok()?))
+    });
+    let subsystem = link_name(node.join("subsystem"));
+
+    DeviceInfo {
+        bus,
+        driver,
+        devnum,
+        subsystem,
+    }
+}
+
+/// Get the vendor ID, product ID and serial number of the USB device a node
+/// belongs to
+pub(super) fn usb_info(path: &Path) -> Option<UsbInfo> {
+    let usb = usb(path)?;
+    let hex = |name| u16::from_str_radix(&read(usb.join(name))?, 16).ok();
+    let vendor = hex("idVendor")?;
+    let product = hex("idProduct")?;
+    let serial = read(usb.join("serial"));
+
+    Some(UsbInfo {
+        vendor,
+        product,
+        serial,
+    })
+}
+
+/// Get the vendor ID of the USB device a node belongs to
+pub(super) fn usb_vendor(path: &Path) -> Option<u16> {
+    let vendor = fs::read_to_string(usb(path)?.join("idVendor")).ok()?;
+
+    u16::from_str_radix(vendor.trim(), 16).ok()
+}
+
+/// Check if a HID device node's report descriptor uses one of these usage
+/// pages
+pub(super) fn hid_usage(path: &Path, pages: &[u16]) -> bool {
+    let Some(node) = node(path) else {
+        return false;
+    };
+    let Ok(descriptor) = fs::read(node.join("device/report_descriptor")) else {
+        return false;
+    };
+
+    uses_page(&descriptor, pages)
+}
+
+/// Check if a HID report descriptor has a Usage Page item for one of these
+/// pages, stopping at the first truncated item
+fn uses_page(descriptor: &[u8], pages: &[u16]) -> bool {
+    let mut items = descriptor;
+
+    // https://www.usb.org/sites/default/files/hid1_11.pdf (6.2.2)
+    while let Some((&prefix, rest)) = items.split_first() {
+        // Long items
+        if prefix == 0xfe {
+            let Some(&len) = rest.first() else { break };
+
+            items = rest.get(usize::from(len) + 2..).unwrap_or_default();
+            continue;
+        }
+
+        let len = match prefix & 0b11 {
+            3 => 4,
+            len => usize::from(len),
+        };
+        let Some(data) = rest.get(..len) else { break };
+
+        // Usage Page is global item tag 0
+        if prefix & 0b1111_1100 == 0b0000_0100 {
+            let page = data
+                .iter()
+                .rev()
+                .fold(0u32, |page, byte| page << 8 | u32::from(*byte));
+
+            if pages.iter().any(|p| u32::from(*p) == page) {
+                return true;
+            }
+        }
+
+        items = &rest[len..];
+    }
+
+    false
+}
+
+/// Get a stable identifier for the USB device a node belongs to, made from its
+/// vendor ID, product ID, and serial number (if it has one)
+pub(super) fn id(path: &Path) -> Option<String> {
+    let usb = usb(path)?;
+    let read = |name| {
+        let value = fs::read_to_string(usb.join(name)).ok()?;
+
+        Some(value.trim().to_string())
+    };
+    let vendor = read("idVendor")?;
+    let product = read("idProduct")?;
+
+    Some(match read("serial") {
+        Some(serial) => format!("usb:{vendor}:{product}:{serial}"),
+        None => format!("usb:{vendor}:{product}"),
+    })
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    /// Combine major and minor numbers, like glibc's `gnu_dev_makedev()`
+    fn makedev(major: u64, minor: u64) -> u64 {
+        (minor & 0xff)
+            | ((major & 0xfff) << 8)
+            | ((minor & !0xff) << 12)
+            | ((major & !0xfff) << 32)
+    }
+
+    #[test]
+    fn split_device_numbers() {
+        assert_eq!(split(makedev(13, 64)), (13, 64));
+        assert_eq!(split(makedev(259, 0x12345)), (259, 0x12345));
+        // The high bits of the major number aren't part of the minor
+        assert_eq!(split(makedev(0xabcde, 0xff_ffff)), (0xabcde, 0xff_ffff));
+        assert_eq!(split(makedev(0xffff_ffff, 0)), (0xffff_ffff, 0));
+    }
+
+    #[test]
+    fn usage_pages() {
+        // Power Device (0x84) and Battery System (0x85)
+        let power = &[0x84, 0x85];
+        let table: [(&str, &[u8], bool); 10] = [
+            ("empty", &[], false),
+            ("one byte", &[0x05, 0x84], true),
+            ("two bytes", &[0x06, 0x85, 0x00], true),
+            ("four bytes", &[0x07, 0x84, 0x00, 0x00, 0x00], true),
+            ("high bytes", &[0x07, 0x84, 0x00, 0x01, 0x00], false),
+            ("other page", &[0x05, 0x01, 0x09, 0x06, 0xa1, 0x01], false),
+            // Usage (local tag 0) with the same data isn't a Usage Page
+            ("usage", &[0x09, 0x84], false),
+            ("after others", &[0x05, 0x01, 0x09, 0x02, 0x05, 0x85], true),
+            ("truncated", &[0x06, 0x84], false),
+            ("truncated after", &[0x05, 0x84, 0x06], true),
+        ];
+
+        for (name, descriptor, expected) in table {
+            assert_eq!(uses_page(descriptor, power), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn braille_page() {
+        let braille = &[0x41];
+        // A braille display's descriptor, with the page sent as one or two
+        // bytes
+        let display = [0x05, 0x41, 0x09, 0x01, 0xa1, 0x01, 0xc0];
+        let wide = [0x06, 0x41, 0x00, 0x09, 0x01, 0xa1, 0x01, 0xc0];
+        // Power devices aren't braille displays, and vice versa
+        let power = [0x05, 0x84, 0x09, 0x04, 0xa1, 0x01, 0xc0];
+
+        assert!(uses_page(&display, braille));
+        assert!(uses_page(&wide, braille));
+        assert!(!uses_page(&power, braille));
+        assert!(!uses_page(&display, &[0x84, 0x85]));
+        // The page is in the high byte, so it's another page
+        assert!(!uses_page(&[0x06, 0x00, 0x41], braille));
+    }
+
+    #[test]
+    fn long_items() {
+        let power = &[0x84, 0x85];
+        // Data that would be a Usage Page if it were read as short items
+        let long = [0xfe, 0x02, 0x10, 0x05, 0x84];
+        let after = [&long[..], &[0x05, 0x85]].concat();
+        let table: [(&str, &[u8], bool); 5] = [
+            ("skipped", &long, false),
+            ("after", &after, true),
+            ("empty", &[0xfe, 0x00, 0x10, 0x05, 0x84], true),
+            ("truncated data", &[0xfe, 0x04, 0x10, 0x05, 0x84], false),
+            ("truncated size", &[0xfe], false),
+        ];
+
+        for (name, descriptor, expected) in table {
+            assert_eq!(uses_page(descriptor, power), expected, "{name}");
+        }
+    }
+}
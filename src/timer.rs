@@ -0,0 +1,115 @@
+#[cfg(not(target_os = "wasi"))]
+use std::sync::{Condvar, Mutex, Once};
+use std::{task::Waker, time::Instant};
+
+use pasts::prelude::*;
+
+/// Deadlines waiting to be woken, along with their wakers
+#[cfg(not(target_os = "wasi"))]
+static DEADLINES: Mutex<Vec<(Instant, Waker)>> = Mutex::new(Vec::new());
+/// Notified whenever a new deadline is added
+#[cfg(not(target_os = "wasi"))]
+static CHANGED: Condvar = Condvar::new();
+/// Starts the timer thread
+#[cfg(not(target_os = "wasi"))]
+static START: Once = Once::new();
+
+/// The timer thread, wakes tasks when their deadlines pass
+#[cfg(not(target_os = "wasi"))]
+fn timers() {
+    let mut deadlines = DEADLINES.lock().unwrap();
+
+    loop {
+        let now = Instant::now();
+
+        deadlines.retain(|(deadline, waker)| {
+            let waiting = *deadline > now;
+
+            if !waiting {
+                waker.wake_by_ref();
+            }
+
+            waiting
+        });
+
+        let Some(next) = deadlines.iter().map(|(deadline, _)| *deadline).min()
+        else {
+            deadlines = CHANGED.wait(deadlines).unwrap();
+            continue;
+        };
+
+        deadlines = CHANGED
+            .wait_timeout(deadlines, next.saturating_duration_since(now))
+            .unwrap()
+            .0;
+    }
+}
+
+/// Register a waker to be woken at the deadline
+#[cfg(not(target_os = "wasi"))]
+fn register(deadline: Instant, waker: &Waker) {
+    START.call_once(|| {
+        std::thread::spawn(timers);
+    });
+
+    let mut deadlines = DEADLINES.lock().unwrap();
+
+    if !deadlines
+        .iter()
+        .any(|(d, w)| *d == deadline && w.will_wake(waker))
+    {
+        deadlines.push((deadline, waker.clone()));
+        CHANGED.notify_one();
+    }
+}
+
+/// Register a waker to be woken at the deadline
+///
+/// WASI doesn't have threads to wake it from, so instead it's woken right away
+/// to check the deadline again when it's next polled, without blocking the
+/// executor.
+#[cfg(target_os = "wasi")]
+fn register(_deadline: Instant, waker: &Waker) {
+    waker.wake_by_ref();
+}
+
+/// Notifier that fires once after its deadline passes
+#[derive(Debug, Default)]
+pub(crate) struct Timer(Option<Instant>);
+
+impl Timer {
+    /// Set (or reset) the deadline
+    pub(crate) fn set(&mut self, deadline: Instant) {
+        self.0 = Some(deadline);
+    }
+
+    /// Check if there's a deadline that hasn't fired yet
+    pub(crate) fn is_set(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Cancel the deadline
+    pub(crate) fn clear(&mut self) {
+        self.0 = None;
+    }
+}
+
+impl Notify for Timer {
+    type Event = ();
+
+    fn poll_next(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll {
+        let this = self.get_mut();
+        let Some(deadline) = this.0 else {
+            return Pending;
+        };
+
+        if Instant::now() >= deadline {
+            this.0 = None;
+            return Ready(());
+        }
+
+        register(deadline, task.waker());
+
+        Pending
+    }
+}
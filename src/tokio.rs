@@ -0,0 +1,153 @@
+use std::{
+    future, io,
+    os::fd::{AsRawFd, RawFd},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pasts::notify::Notify;
+use tokio::io::{unix::AsyncFd, Interest};
+
+use crate::{Found, Searcher};
+
+/// A searcher that has a file descriptor, for registering with tokio
+#[derive(Debug)]
+struct Registered(Searcher);
+
+impl AsRawFd for Registered {
+    fn as_raw_fd(&self) -> RawFd {
+        // Only registered when it has one, and never closed while it is
+        self.0.as_fd().map_or(-1, |fd| fd.as_raw_fd())
+    }
+}
+
+#[derive(Debug)]
+enum Inner {
+    /// Woken by tokio's reactor
+    Registered(AsyncFd<Registered>),
+    /// The backend doesn't have a file descriptor, so it's only woken by
+    /// lookit's timers
+    Polled(Searcher),
+}
+
+/// Lookit searcher woken by tokio.  Yields each device [`Found`].
+///
+/// Created with
+/// [`SearcherBuilder::build_tokio()`](crate::SearcherBuilder::build_tokio),
+/// which registers the searcher's file descriptor with tokio's [`AsyncFd`]
+/// instead of smelling_salts, so there isn't a second reactor thread.
+#[derive(Debug)]
+pub struct TokioSearcher {
+    inner: Inner,
+}
+
+impl TokioSearcher {
+    pub(crate) fn new(searcher: Searcher) -> io::Result<Self> {
+        let inner = if searcher.as_fd().is_some() {
+            let searcher = Registered(searcher);
+
+            Inner::Registered(AsyncFd::with_interest(
+                searcher,
+                Interest::READABLE,
+            )?)
+        } else {
+            Inner::Polled(searcher)
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Wait for the next device to be found.
+    ///
+    /// Only fails if tokio's reactor has shut down.
+    pub async fn next(&mut self) -> io::Result<Found> {
+        future::poll_fn(|cx| self.poll_next(cx)).await
+    }
+
+    /// Poll for the next device to be found, for implementing futures and
+    /// streams around the searcher.
+    pub fn poll_next(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<Found>> {
+        let fd = match self.inner {
+            Inner::Registered(ref mut fd) => fd,
+            Inner::Polled(ref mut searcher) => {
+                return Pin::new(searcher).poll_next(cx).map(Ok);
+            }
+        };
+
+        loop {
+            // Readiness is taken before reading, so events that come in while
+            // the searcher reads aren't cleared along with it
+            let mut guard = match fd.poll_read_ready_mut(cx) {
+                Poll::Ready(guard) => guard?,
+                // Devices found earlier, or after a delay, may be waiting
+                Poll::Pending => {
+                    return Pin::new(&mut fd.get_mut().0).poll_next(cx).map(Ok);
+                }
+            };
+
+            if let Poll::Ready(found) =
+                Pin::new(&mut guard.get_inner_mut().0).poll_next(cx)
+            {
+                return Poll::Ready(Ok(found));
+            }
+
+            guard.clear_ready();
+        }
+    }
+
+    /// Deregister the searcher from tokio, and get it back.
+    ///
+    /// It's still not watched by smelling_salts, so only poll it once its
+    /// [file descriptor](Searcher::as_fd) is readable.
+    pub fn into_inner(self) -> Searcher {
+        match self.inner {
+            Inner::Registered(fd) => fd.into_inner().0,
+            Inner::Polled(searcher) => searcher,
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::{fs, thread, time::Duration};
+
+    use crate::SearcherBuilder;
+
+    #[test]
+    fn yields_devices() {
+        let dir = std::env::temp_dir()
+            .join(format!("lookit-tokio-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir(&dir).unwrap();
+        fs::write(dir.join("dev0"), "").unwrap();
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_io()
+            .build()
+            .unwrap();
+        let path = format!("{}/", dir.display());
+        let node = dir.join("dev1");
+        let adding = node.clone();
+        let (initial, added) = runtime.block_on(async {
+            let mut searcher = SearcherBuilder::with_path(path, ["dev"])
+                .build_tokio()
+                .unwrap();
+            let initial = searcher.next().await.unwrap();
+
+            // Added while the task is waiting
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(100));
+                fs::write(adding, "").unwrap();
+            });
+
+            (initial, searcher.next().await.unwrap())
+        });
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(initial.path(), dir.join("dev0"));
+        assert_eq!(added.path(), node);
+    }
+}
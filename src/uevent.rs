@@ -0,0 +1,188 @@
+use std::{
+    collections::VecDeque,
+    ffi::OsStr,
+    io::{self, Read},
+    mem::{self, size_of},
+    os::{
+        fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+        raw::{c_int, c_void},
+        unix::ffi::OsStrExt,
+    },
+    path::{Path, PathBuf},
+};
+
+use pasts::prelude::*;
+
+use super::listener::Listener;
+
+// https://github.com/torvalds/linux/blob/dbad9ce9397ef7f891b4ff44bad694add673c1a1/include/uapi/linux/netlink.h
+
+const AF_NETLINK: c_int = 16;
+const SOCK_DGRAM: c_int = 2;
+const SOCK_NONBLOCK: c_int = 0o4000;
+const SOCK_CLOEXEC: c_int = 0o2000000;
+const NETLINK_KOBJECT_UEVENT: c_int = 15;
+
+/// Multicast group for uevents sent by the kernel (rather than udev)
+const KERNEL_GROUP: u32 = 1;
+
+/// struct sockaddr_nl, from C.
+#[repr(C)]
+struct SockaddrNl {
+    nl_family: u16,
+    nl_pad: u16,
+    nl_pid: u32,
+    nl_groups: u32,
+}
+
+extern "C" {
+    fn socket(domain: c_int, kind: c_int, protocol: c_int) -> RawFd;
+    fn bind(fd: RawFd, addr: *const c_void, len: u32) -> c_int;
+}
+
+/// Listens for kernel uevents over netlink
+#[derive(Debug)]
+pub(super) struct Uevent {
+    device: Listener,
+    buffer: Vec<u8>,
+    /// Paths of the device last added, not yet returned
+    queue: VecDeque<PathBuf>,
+    /// Paths of devices removed, not yet taken
+    removed: VecDeque<PathBuf>,
+}
+
+impl Uevent {
+    pub(super) fn new(watched: bool) -> io::Result<Self> {
+        let fd = unsafe {
+            socket(
+                AF_NETLINK,
+                SOCK_DGRAM | SOCK_NONBLOCK | SOCK_CLOEXEC,
+                NETLINK_KOBJECT_UEVENT,
+            )
+        };
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        let addr = SockaddrNl {
+            nl_family: AF_NETLINK as u16,
+            nl_pad: 0,
+            nl_pid: 0,
+            nl_groups: KERNEL_GROUP,
+        };
+        let len = size_of::<SockaddrNl>() as u32;
+        let ptr: *const SockaddrNl = &addr;
+
+        if unsafe { bind(fd.as_raw_fd(), ptr.cast(), len) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Self::with_fd(fd, watched))
+    }
+
+    /// Listen on an already bound socket
+    pub(super) fn with_fd(fd: OwnedFd, watched: bool) -> Self {
+        let device = Listener::new(fd, watched);
+        let buffer = vec![0; 8192];
+        let queue = VecDeque::new();
+        let removed = VecDeque::new();
+
+        Self {
+            device,
+            buffer,
+            queue,
+            removed,
+        }
+    }
+
+    /// Get the socket
+    pub(super) fn device(&self) -> &Listener {
+        &self.device
+    }
+
+    /// Get the socket, and paths not yet returned
+    pub(super) fn into_parts(self) -> (Listener, VecDeque<PathBuf>) {
+        (self.device, self.queue)
+    }
+
+    /// Parse the paths of a device added or removed out of a uevent,
+    /// returning whether it was removed
+    ///
+    /// These are the device node (if it has one), and the device's directory
+    /// in `/sys/class/` (for devices without nodes, like Bluetooth adapters).
+    /// For hotplug events (like a monitor being plugged into a GPU), only the
+    /// device's directory is returned, since the device itself isn't new.
+    fn parse(message: &[u8]) -> (Vec<PathBuf>, bool) {
+        let mut add = false;
+        let mut remove = false;
+        let mut hotplug = false;
+        let mut devname = None;
+        let mut devpath = None;
+        let mut subsystem = None;
+
+        // Names are kept as bytes, since they don't have to be UTF-8
+        for field in message.split(|n| *n == b'\0') {
+            if field == b"ACTION=add" {
+                add = true;
+            } else if field == b"ACTION=remove" {
+                remove = true;
+            } else if field == b"HOTPLUG=1" {
+                hotplug = true;
+            } else if let Some(name) = field.strip_prefix(b"DEVNAME=") {
+                // Relative names are joined onto /dev/, absolute ones replace
+                // it
+                devname =
+                    Some(Path::new("/dev/").join(OsStr::from_bytes(name)));
+            } else if let Some(path) = field.strip_prefix(b"DEVPATH=") {
+                devpath = Path::new(OsStr::from_bytes(path)).file_name();
+            } else if let Some(name) = field.strip_prefix(b"SUBSYSTEM=") {
+                subsystem = Some(OsStr::from_bytes(name));
+            }
+        }
+
+        let class = subsystem.zip(devpath).map(|(subsystem, name)| {
+            Path::new("/sys/class/").join(subsystem).join(name)
+        });
+
+        if hotplug && !add {
+            return (class.into_iter().collect(), false);
+        }
+
+        if !add && !remove {
+            return (Vec::new(), false);
+        }
+
+        (devname.into_iter().chain(class).collect(), remove)
+    }
+
+    /// Take the paths of devices removed since last time
+    pub(super) fn take_removed(&mut self) -> VecDeque<PathBuf> {
+        mem::take(&mut self.removed)
+    }
+
+    /// Get the path of the next device added
+    pub(super) fn poll_added(&mut self, task: &mut Task<'_>) -> Poll<PathBuf> {
+        loop {
+            if let Some(path) = self.queue.pop_front() {
+                return Ready(path);
+            }
+
+            // Read messages until one is ready, then wait for more
+            if let Ok(len) = self.device.read(&mut self.buffer) {
+                let (paths, removed) = Self::parse(&self.buffer[..len]);
+
+                if removed {
+                    self.removed.extend(paths);
+                } else {
+                    self.queue.extend(paths);
+                }
+
+                continue;
+            }
+
+            if Pin::new(&mut self.device).poll_next(task).is_pending() {
+                return Pending;
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use pasts::prelude::*;
+
+use crate::{
+    timer::Timer, Access, ConnectOptions, Device, Error, Found, Interface,
+    Platform,
+};
+
+/// How often to retry when closes can't be watched for (or are missed)
+const PROBE: Duration = Duration::from_secs(1);
+
+#[cfg(target_os = "wasi")]
+const EBUSY: i32 = 10;
+#[cfg(not(target_os = "wasi"))]
+const EBUSY: i32 = 16;
+
+/// Lookit [`Future`].  Connects to a [`Found`] device once it's not busy.
+pub struct Waitlist {
+    found: Option<Found>,
+    options: ConnectOptions,
+    /// Whether or not watching for closes has been attempted
+    watched: bool,
+    closed: Option<<Platform as Interface>::Closed>,
+    timer: Timer,
+}
+
+impl fmt::Debug for Waitlist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Waitlist")
+            .field("found", &self.found)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Waitlist {
+    pub(crate) fn new(found: Found) -> Self {
+        let found = Some(found);
+        let options = ConnectOptions::new();
+        let watched = false;
+        let closed = None;
+        let timer = Timer::default();
+
+        Self {
+            found,
+            options,
+            watched,
+            closed,
+            timer,
+        }
+    }
+
+    /// Connect to the device for input only
+    pub fn input(mut self) -> Self {
+        self.options = self.options.input();
+        self
+    }
+
+    /// Connect to the device for output only
+    pub fn output(mut self) -> Self {
+        self.options = self.options.output();
+        self
+    }
+}
+
+impl Future for Waitlist {
+    type Output = Result<(Device, Access), Error>;
+
+    fn poll(self: Pin<&mut Self>, task: &mut Task<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            let found = this.found.take().expect("polled after completion");
+
+            match Platform::open(found, &this.options) {
+                Ok(device) => {
+                    return Ready(Ok((device, this.options.access())))
+                }
+                Err((found, e)) if e.raw_os_error() == Some(EBUSY) => {
+                    this.found = Some(found);
+                }
+                Err((found, e)) => return Ready(Err(Error::new(found, e))),
+            }
+
+            // Start watching before retrying, so a close isn't missed
+            if !this.watched {
+                this.watched = true;
+                this.closed = this
+                    .found
+                    .as_ref()
+                    .and_then(|found| found.with_path(Platform::watch_close));
+                continue;
+            }
+
+            let mut retry = false;
+
+            if let Some(ref mut closed) = this.closed {
+                retry = Pin::new(closed).poll_next(task).is_ready();
+            }
+
+            if !this.timer.is_set() {
+                this.timer.set(Instant::now() + PROBE);
+            }
+
+            if Pin::new(&mut this.timer).poll_next(task).is_ready() {
+                retry = true;
+            }
+
+            if !retry {
+                return Pending;
+            }
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::{
+    collections::VecDeque,
+    fs::{self, OpenOptions},
+    io,
+    os::fd::{BorrowedFd, OwnedFd},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use smelling_salts::Watch;
+
+use crate::{
+    devfs,
+    scan::{self, Scanner},
+    Attempt, Backend, ConnectOptions, Device, DeviceInfo, Dir, Events, Feature,
+    Found, Interface, Kind, Platform, Restriction, SearcherBuilder, Snapshot,
+    Target, UsbInfo, VideoInfo,
+};
+
+/// How often device directories get rescanned
+///
+/// WASI doesn't have a way to watch directories, so they must be polled.
+const INTERVAL: Duration = Duration::from_secs(1);
+
+impl Interface for Platform {
+    type Closed = pasts::notify::Pending<()>;
+    type Searcher = Scanner;
+
+    fn searcher(
+        builder: &SearcherBuilder,
+        attempts: &mut Vec<Attempt>,
+    ) -> Option<Scanner> {
+        if !builder.allows(Backend::Scan) {
+            return None;
+        }
+
+        let dirs = dirs(&builder.target);
+        let mut scanner = if dirs.is_empty() {
+            // Records that devices of this kind can't be found
+            Scanner::new(dirs, INTERVAL, attempts)
+        } else {
+            dirs.into_iter()
+                .find_map(|dir| Scanner::new(vec![dir], INTERVAL, attempts))
+        }?;
+
+        if !builder.initial {
+            scanner.skip_initial();
+        }
+
+        Some(scanner)
+    }
+
+    fn backend(_searcher: &Scanner) -> Backend {
+        Backend::Scan
+    }
+
+    fn restrictions(_attempts: &[Attempt]) -> Vec<Restriction> {
+        let mut restrictions = Vec::new();
+
+        if fs::read_dir("/dev").is_err() {
+            restrictions.push(Restriction::Devices);
+        }
+
+        restrictions
+    }
+
+    fn open(
+        found: Found,
+        options: &ConnectOptions,
+    ) -> Result<Device, (Found, io::Error)> {
+        use Events::*;
+        let watch = match options.events {
+            Read() => Watch::INPUT,
+            Write() => Watch::OUTPUT,
+            All() => Watch::INPUT.output(),
+        };
+
+        Ok(Device::new(Self::open_fd(found, options)?, watch))
+    }
+
+    fn open_fd(
+        found: Found,
+        options: &ConnectOptions,
+    ) -> Result<OwnedFd, (Found, io::Error)> {
+        use Events::*;
+        let (read, write) = match options.events {
+            Read() => (true, false),
+            Write() => (false, true),
+            All() => (true, true),
+        };
+        let file = match OpenOptions::new()
+            .read(read)
+            .write(write)
+            .append(write && options.append)
+            .open(&found.path)
+        {
+            Ok(file) => file,
+            Err(error) => return Err((found, error)),
+        };
+
+        Ok(OwnedFd::from(file))
+    }
+
+    fn parent(_path: &Path) -> Option<String> {
+        None
+    }
+
+    fn id(_path: &Path) -> Option<String> {
+        None
+    }
+
+    fn stable_path(_path: &Path) -> Option<PathBuf> {
+        None
+    }
+
+    fn settled(_path: &Path) -> bool {
+        true
+    }
+
+    fn port(_path: &Path) -> Option<String> {
+        None
+    }
+
+    fn name(_path: &Path) -> Option<String> {
+        None
+    }
+
+    fn partition(_path: &Path) -> bool {
+        false
+    }
+
+    fn index(_path: &Path) -> Option<u32> {
+        None
+    }
+
+    fn usb_class(_path: &Path, _class: u8) -> bool {
+        false
+    }
+
+    fn usb_vendor(_path: &Path) -> Option<u16> {
+        None
+    }
+
+    fn usb_info(_path: &Path) -> Option<UsbInfo> {
+        None
+    }
+
+    fn device_info(_path: &Path) -> DeviceInfo {
+        DeviceInfo::default()
+    }
+
+    fn hid_usage(_path: &Path, _pages: &[u16]) -> bool {
+        false
+    }
+
+    fn supports(_path: &Path, _feature: Feature) -> bool {
+        false
+    }
+
+    fn video_info(_path: &Path) -> io::Result<VideoInfo> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    fn fd(_searcher: &Scanner) -> Option<BorrowedFd<'_>> {
+        None
+    }
+
+    fn take_removed(searcher: &mut Scanner) -> VecDeque<PathBuf> {
+        searcher.take_removed()
+    }
+
+    fn watch_close(_path: &Path) -> Option<pasts::notify::Pending<()>> {
+        None
+    }
+
+    fn watch_removal(_path: &Path) -> Option<pasts::notify::Pending<()>> {
+        None
+    }
+
+    fn enumerate(kind: Kind) -> Vec<PathBuf> {
+        scan::list(&dirs(&Target::Kind(kind))).into_iter().collect()
+    }
+
+    fn save(searcher: Scanner, snapshot: &mut Snapshot) {
+        searcher.save(snapshot);
+    }
+
+    unsafe fn restore(snapshot: &Snapshot) -> Option<Scanner> {
+        let dir = dirs(&snapshot.target)
+            .into_iter()
+            .find(|dir| Some(&*dir.path) == snapshot.dir.as_deref())?;
+
+        Some(Scanner::restore(vec![dir], INTERVAL, snapshot))
+    }
+}
+
+/// Directories where device nodes for a target are found, to try in order
+fn dirs(target: &Target) -> Vec<Dir> {
+    match target {
+        Target::Custom(dir) => vec![dir.clone()],
+        Target::Kind(kind) if devfs::SYSFS_ONLY.contains(kind) => Vec::new(),
+        Target::Kind(kind) => devfs::dirs(*kind),
+    }
+}